@@ -19,52 +19,482 @@ along with this program.  If not, see <https://www.gnu.org/licenses/>.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Day01Error {
     ParseFailure(String),
+    ReadFailure(String),
 }
 
-pub fn parse_depth(line: &str) -> Result<u32, Day01Error> {
-    line.parse::<u32>()
+impl std::fmt::Display for Day01Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day01Error::ParseFailure(mass) => {
+                write!(f, "Failed to parse {} as a mass value", mass)
+            }
+            Day01Error::ReadFailure(why) => {
+                write!(f, "Failed to read depth data: {}", why)
+            }
+        }
+    }
+}
+
+pub trait Depth: Copy + PartialOrd + std::str::FromStr + std::fmt::Display {
+    fn to_f64(self) -> f64;
+}
+
+impl Depth for u32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Depth for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Depth for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthMode {
+    Unsigned,
+    Signed,
+    Decimal,
+}
+
+impl std::str::FromStr for DepthMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unsigned" => Ok(DepthMode::Unsigned),
+            "signed" => Ok(DepthMode::Signed),
+            "decimal" => Ok(DepthMode::Decimal),
+            _ => Err(format!(
+                "Unknown depth mode {}, expected unsigned, signed or decimal",
+                s
+            )),
+        }
+    }
+}
+
+pub const DEFAULT_WINDOW: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    pub window: usize,
+    pub mode: DepthMode,
+    pub tolerance: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            window: DEFAULT_WINDOW,
+            mode: DepthMode::Unsigned,
+            tolerance: 0.0,
+        }
+    }
+}
+
+pub fn parse_depth_as<T: Depth>(line: &str) -> Result<T, Day01Error> {
+    line.trim()
+        .parse::<T>()
         .ok()
+        .filter(|d| d.to_f64().is_finite())
         .ok_or(Day01Error::ParseFailure(line.to_string()))
 }
 
+pub fn parse_as<T: Depth>(data: &str) -> Result<Vec<T>, Day01Error> {
+    data.lines()
+        .map(parse_depth_as)
+        .collect::<Result<Vec<T>, Day01Error>>()
+}
+
+pub fn parse_depth(line: &str) -> Result<u32, Day01Error> {
+    parse_depth_as(line)
+}
+
 pub fn parse(data: &str) -> Result<Vec<u32>, Day01Error> {
     data.lines()
         .map(parse_depth)
         .collect::<Result<Vec<u32>, Day01Error>>()
 }
 
-pub fn solution1(data: &Vec<u32>) -> String {
-    return data
-        .iter()
-        .zip(data.iter().skip(1))
-        .filter(|(x, y)| x < y)
-        .count()
-        .to_string();
+// A reading only counts as an increase when it is deeper by more than the tolerance, so
+// noisy fractional sensors do not register every tiny wobble.
+pub fn is_increase<T: Depth>(from: T, to: T, tolerance: f64) -> bool {
+    if tolerance == 0.0 {
+        return from < to;
+    }
+
+    to.to_f64() - from.to_f64() > tolerance
 }
 
-pub fn solution2(data: &Vec<u32>) -> String {
-    let slide = data
+pub fn count_window_increases_with_tolerance<T: Depth>(
+    depths: &[T],
+    window: usize,
+    tolerance: f64,
+) -> usize {
+    depths
         .iter()
-        .zip(data.iter().skip(1))
-        .zip(data.iter().skip(2))
-        .map(|((x, y), z)| x + y + z);
-
-    return slide
-        .clone()
-        .zip(slide.skip(1))
-        .filter(|(x, y)| x < y)
+        .zip(depths.iter().skip(window))
+        .filter(|(&x, &y)| is_increase(x, y, tolerance))
         .count()
-        .to_string();
+}
+
+// Consecutive windows share all but their first and last readings, so comparing the sums
+// of the windows starting at i and i + 1 is the same as comparing depths[i] with
+// depths[i + window].
+pub fn count_window_increases<T: Depth>(depths: &[T], window: usize) -> usize {
+    count_window_increases_with_tolerance(depths, window, 0.0)
+}
+
+// Keeps only the last `window` readings, so arbitrarily long inputs are analysed in
+// constant memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SonarSweep<T: Depth> {
+    window: usize,
+    tolerance: f64,
+    recent: std::collections::VecDeque<T>,
+    last: Option<T>,
+    increases: usize,
+    window_increases: usize,
+}
+
+impl<T: Depth> SonarSweep<T> {
+    pub fn new(window: usize) -> Self {
+        SonarSweep {
+            window,
+            tolerance: 0.0,
+            recent: std::collections::VecDeque::with_capacity(window + 1),
+            last: None,
+            increases: 0,
+            window_increases: 0,
+        }
+    }
+
+    pub fn with_tolerance(window: usize, tolerance: f64) -> Self {
+        SonarSweep {
+            tolerance,
+            ..SonarSweep::new(window)
+        }
+    }
+
+    pub fn push(&mut self, depth: T) {
+        if let Some(last) = self.last {
+            if is_increase(last, depth, self.tolerance) {
+                self.increases += 1;
+            }
+        }
+        self.last = Some(depth);
+
+        self.recent.push_back(depth);
+        if self.recent.len() > self.window {
+            if let Some(oldest) = self.recent.pop_front() {
+                if is_increase(oldest, depth, self.tolerance) {
+                    self.window_increases += 1;
+                }
+            }
+        }
+    }
+
+    pub fn increases(&self) -> usize {
+        self.increases
+    }
+
+    pub fn window_increases(&self) -> usize {
+        self.window_increases
+    }
+}
+
+impl<T: Depth> Extend<T> for SonarSweep<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for depth in iter {
+            self.push(depth);
+        }
+    }
+}
+
+pub fn analyse_stream<T: Depth, R: std::io::BufRead>(
+    reader: R,
+    window: usize,
+    tolerance: f64,
+) -> Result<SonarSweep<T>, Day01Error> {
+    let mut sweep = SonarSweep::with_tolerance(window, tolerance);
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| Day01Error::ReadFailure(e.to_string()))?;
+        sweep.push(parse_depth_as(&line)?);
+    }
+
+    return Ok(sweep);
+}
+
+pub fn solution1(data: &[u32]) -> String {
+    return count_window_increases(data, 1).to_string();
+}
+
+pub fn solution2(data: &[u32]) -> String {
+    return count_window_increases(data, DEFAULT_WINDOW).to_string();
+}
+
+fn solutions_as<T: Depth>(data: &str, options: &Options) -> Result<(String, String), String> {
+    let depths = parse_as::<T>(data).map_err(|e| e.to_string())?;
+
+    return Ok((
+        count_window_increases_with_tolerance(&depths, 1, options.tolerance).to_string(),
+        count_window_increases_with_tolerance(&depths, options.window, options.tolerance)
+            .to_string(),
+    ));
+}
+
+pub fn solutions_with(data: &str, options: &Options) -> Result<(String, String), String> {
+    match options.mode {
+        DepthMode::Unsigned => solutions_as::<u32>(data, options),
+        DepthMode::Signed => solutions_as::<i64>(data, options),
+        DepthMode::Decimal => solutions_as::<f64>(data, options),
+    }
 }
 
 pub fn solutions(data: &str) -> Result<(String, String), String> {
-    let masses = parse(data).map_err(|Day01Error::ParseFailure(mass)| {
-        format!("Failed to parse {} as a mass value", mass)
-    })?;
+    let masses = parse(data).map_err(|e| e.to_string())?;
 
     return Ok((solution1(&masses), solution2(&masses)));
 }
 
+pub const DEFAULT_ANOMALY_THRESHOLD: f64 = 50.0;
+
+// a run of readings that each rise or fall by more than the tolerance, covering `length`
+// readings from `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Run {
+    pub start: usize,
+    pub length: usize,
+}
+
+// the step from reading `index - 1` to reading `index`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Jump<T: Depth> {
+    pub index: usize,
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: Depth> Jump<T> {
+    pub fn size(&self) -> f64 {
+        self.to.to_f64() - self.from.to_f64()
+    }
+}
+
+// a reading that deviates from the average of the `window` readings before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anomaly<T: Depth> {
+    pub index: usize,
+    pub depth: T,
+    pub average: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthReport<T: Depth> {
+    pub min: T,
+    pub max: T,
+    pub mean: f64,
+    pub longest_increase: Run,
+    pub longest_decrease: Run,
+    pub largest_rise: Option<Jump<T>>,
+    pub largest_fall: Option<Jump<T>>,
+    pub anomalies: Vec<Anomaly<T>>,
+}
+
+pub fn longest_run<T: Depth>(depths: &[T], step: &dyn Fn(T, T) -> bool) -> Run {
+    let mut longest = Run::default();
+    let mut current = Run::default();
+
+    for (i, &depth) in depths.iter().enumerate() {
+        if i > 0 && step(depths[i - 1], depth) {
+            current.length += 1;
+        } else {
+            current = Run {
+                start: i,
+                length: 1,
+            };
+        }
+
+        if current.length > longest.length {
+            longest = current;
+        }
+    }
+
+    return longest;
+}
+
+pub fn rolling_anomalies<T: Depth>(depths: &[T], window: usize, threshold: f64) -> Vec<Anomaly<T>> {
+    if window == 0 {
+        return Vec::new();
+    }
+
+    let mut sum: f64 = depths.iter().take(window).map(|d| d.to_f64()).sum();
+    let mut anomalies = Vec::new();
+
+    for index in window..depths.len() {
+        let average = sum / window as f64;
+        let depth = depths[index];
+        if (depth.to_f64() - average).abs() > threshold {
+            anomalies.push(Anomaly {
+                index,
+                depth,
+                average,
+            });
+        }
+
+        sum = sum + depth.to_f64() - depths[index - window].to_f64();
+    }
+
+    return anomalies;
+}
+
+impl<T: Depth> DepthReport<T> {
+    pub fn new(
+        depths: &[T],
+        window: usize,
+        threshold: f64,
+        tolerance: f64,
+    ) -> Option<DepthReport<T>> {
+        let first = *depths.first()?;
+        let min = depths
+            .iter()
+            .fold(first, |min, &d| if d < min { d } else { min });
+        let max = depths
+            .iter()
+            .fold(first, |max, &d| if d > max { d } else { max });
+        let mean = depths.iter().map(|d| d.to_f64()).sum::<f64>() / depths.len() as f64;
+
+        let jumps = depths.windows(2).enumerate().map(|(i, pair)| Jump {
+            index: i + 1,
+            from: pair[0],
+            to: pair[1],
+        });
+        let largest_rise =
+            jumps
+                .clone()
+                .filter(|j| j.size() > 0.0)
+                .fold(None, |best: Option<Jump<T>>, j| match best {
+                    Some(b) if b.size() >= j.size() => Some(b),
+                    _ => Some(j),
+                });
+        let largest_fall =
+            jumps
+                .filter(|j| j.size() < 0.0)
+                .fold(None, |best: Option<Jump<T>>, j| match best {
+                    Some(b) if b.size() <= j.size() => Some(b),
+                    _ => Some(j),
+                });
+
+        Some(DepthReport {
+            min,
+            max,
+            mean,
+            longest_increase: longest_run(depths, &|a, b| is_increase(a, b, tolerance)),
+            longest_decrease: longest_run(depths, &|a, b| is_increase(b, a, tolerance)),
+            largest_rise,
+            largest_fall,
+            anomalies: rolling_anomalies(depths, window, threshold),
+        })
+    }
+}
+
+impl<T: Depth> std::fmt::Display for DepthReport<T> {
+    // readings are numbered from 1 so they match line numbers in the input
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "Minimum depth: {}", self.min)?;
+        writeln!(f, "Maximum depth: {}", self.max)?;
+        writeln!(f, "Mean depth: {:.2}", self.mean)?;
+        writeln!(
+            f,
+            "Longest increasing run: {} readings starting at reading {}",
+            self.longest_increase.length,
+            self.longest_increase.start + 1
+        )?;
+        writeln!(
+            f,
+            "Longest decreasing run: {} readings starting at reading {}",
+            self.longest_decrease.length,
+            self.longest_decrease.start + 1
+        )?;
+
+        for (name, jump) in [("rise", self.largest_rise), ("fall", self.largest_fall)] {
+            match jump {
+                Some(j) => writeln!(
+                    f,
+                    "Largest {}: {} -> {} ({:+}) at reading {}",
+                    name,
+                    j.from,
+                    j.to,
+                    j.size(),
+                    j.index + 1
+                )?,
+                None => writeln!(f, "Largest {}: none", name)?,
+            }
+        }
+
+        write!(f, "Anomalies: {}", self.anomalies.len())?;
+        for anomaly in self.anomalies.iter() {
+            write!(
+                f,
+                "\n  reading {}: {} against rolling average {:.2}",
+                anomaly.index + 1,
+                anomaly.depth,
+                anomaly.average
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn report_as<T: Depth>(data: &str, options: &Options, threshold: f64) -> Result<String, String> {
+    let depths = parse_as::<T>(data).map_err(|e| e.to_string())?;
+    let report = DepthReport::new(&depths, options.window, threshold, options.tolerance)
+        .ok_or("Unable to report on empty depth data".to_string())?;
+
+    return Ok(report.to_string());
+}
+
+pub fn report(data: &str, options: &Options, threshold: f64) -> Result<String, String> {
+    match options.mode {
+        DepthMode::Unsigned => report_as::<u32>(data, options, threshold),
+        DepthMode::Signed => report_as::<i64>(data, options, threshold),
+        DepthMode::Decimal => report_as::<f64>(data, options, threshold),
+    }
+}
+
+fn stream_solutions_as<T: Depth, R: std::io::BufRead>(
+    reader: R,
+    options: &Options,
+) -> Result<(String, String), String> {
+    let sweep = analyse_stream::<T, R>(reader, options.window, options.tolerance)
+        .map_err(|e| e.to_string())?;
+
+    return Ok((
+        sweep.increases().to_string(),
+        sweep.window_increases().to_string(),
+    ));
+}
+
+pub fn stream_solutions<R: std::io::BufRead>(
+    reader: R,
+    options: &Options,
+) -> Result<(String, String), String> {
+    match options.mode {
+        DepthMode::Unsigned => stream_solutions_as::<u32, R>(reader, options),
+        DepthMode::Signed => stream_solutions_as::<i64, R>(reader, options),
+        DepthMode::Decimal => stream_solutions_as::<f64, R>(reader, options),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -104,6 +534,287 @@ mod tests {
         assert_eq!(solution2(data), "5")
     }
 
+    #[test]
+    fn test_count_window_increases() {
+        let data = &parse(EXAMPLE).unwrap();
+        assert_eq!(count_window_increases(data, 1), 7);
+        assert_eq!(count_window_increases(data, 3), 5);
+        assert_eq!(count_window_increases(data, 5), 5);
+        assert_eq!(count_window_increases(data, 10), 0);
+        assert_eq!(count_window_increases(data, 0), 0);
+        assert_eq!(count_window_increases::<u32>(&[], 3), 0);
+
+        // matches summing every window explicitly
+        let sums = data
+            .windows(5)
+            .map(|w| w.iter().sum())
+            .collect::<Vec<u32>>();
+        assert_eq!(
+            count_window_increases(data, 5),
+            count_window_increases(&sums, 1)
+        );
+    }
+
+    #[test]
+    fn test_solutions_with() {
+        let options = Options {
+            window: 1,
+            ..Options::default()
+        };
+        assert_eq!(
+            solutions_with(EXAMPLE, &options),
+            Ok(("7".to_string(), "7".to_string()))
+        );
+
+        let options = Options {
+            window: 5,
+            ..Options::default()
+        };
+        assert_eq!(
+            solutions_with(EXAMPLE, &options),
+            Ok(("7".to_string(), "5".to_string()))
+        );
+
+        let signed = "-3\n-5\n-1\n2\n-4";
+        assert_eq!(
+            solutions_with(signed, &Options::default()),
+            Err("Failed to parse -3 as a mass value".to_string())
+        );
+        let options = Options {
+            mode: DepthMode::Signed,
+            ..Options::default()
+        };
+        assert_eq!(
+            solutions_with(signed, &options),
+            Ok(("2".to_string(), "2".to_string()))
+        );
+        assert_eq!(
+            stream_solutions(signed.as_bytes(), &options),
+            Ok(("2".to_string(), "2".to_string()))
+        );
+
+        let decimal = "1.0\n1.05\n1.5\n1.45\n2.0";
+        let options = Options {
+            window: 2,
+            mode: DepthMode::Decimal,
+            tolerance: 0.0,
+        };
+        assert_eq!(
+            solutions_with(decimal, &options),
+            Ok(("3".to_string(), "3".to_string()))
+        );
+        let options = Options {
+            tolerance: 0.1,
+            ..options
+        };
+        assert_eq!(
+            solutions_with(decimal, &options),
+            Ok(("2".to_string(), "3".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_depth_as() {
+        assert_eq!(parse_depth_as::<i64>("-12"), Ok(-12));
+        assert_eq!(parse_depth_as::<f64>("3.25"), Ok(3.25));
+        assert_eq!(
+            parse_depth_as::<u32>("-12"),
+            Err(Day01Error::ParseFailure("-12".to_string()))
+        );
+        assert_eq!(
+            parse_depth_as::<f64>("NaN"),
+            Err(Day01Error::ParseFailure("NaN".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_is_increase() {
+        assert_eq!(is_increase(1u32, 2, 0.0), true);
+        assert_eq!(is_increase(2u32, 2, 0.0), false);
+        assert_eq!(is_increase(-5i64, -4, 0.0), true);
+        assert_eq!(is_increase(1.0, 1.05, 0.1), false);
+        assert_eq!(is_increase(1.0, 1.2, 0.1), true);
+    }
+
+    #[test]
+    fn test_depth_mode_from_str() {
+        assert_eq!("signed".parse(), Ok(DepthMode::Signed));
+        assert_eq!("decimal".parse(), Ok(DepthMode::Decimal));
+        assert_eq!("unsigned".parse(), Ok(DepthMode::Unsigned));
+        assert!("complex".parse::<DepthMode>().is_err());
+    }
+
+    #[test]
+    fn test_sonar_sweep() {
+        let data = parse(EXAMPLE).unwrap();
+        let mut sweep = SonarSweep::new(3);
+        assert_eq!(sweep.increases(), 0);
+
+        sweep.extend(data.iter().take(4).copied());
+        assert_eq!(sweep.increases(), 3);
+        assert_eq!(sweep.window_increases(), 1);
+
+        sweep.extend(data.iter().skip(4).copied());
+        assert_eq!(sweep.increases(), 7);
+        assert_eq!(sweep.window_increases(), 5);
+
+        for window in 0..=12 {
+            let mut sweep = SonarSweep::new(window);
+            sweep.extend(data.iter().copied());
+            assert_eq!(
+                sweep.window_increases(),
+                count_window_increases(&data, window),
+                "window {}",
+                window
+            );
+            assert_eq!(sweep.increases(), 7);
+        }
+    }
+
+    #[test]
+    fn test_analyse_stream() {
+        let sweep = analyse_stream::<u32, _>(EXAMPLE.as_bytes(), 3, 0.0).unwrap();
+        assert_eq!(sweep.increases(), 7);
+        assert_eq!(sweep.window_increases(), 5);
+
+        assert_eq!(
+            analyse_stream::<u32, _>("4\nhi\n6".as_bytes(), 3, 0.0),
+            Err(Day01Error::ParseFailure("hi".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_stream_solutions() {
+        assert_eq!(
+            stream_solutions(EXAMPLE.as_bytes(), &Options::default()),
+            solutions(EXAMPLE)
+        );
+        assert_eq!(
+            stream_solutions("invalid".as_bytes(), &Options::default()),
+            Err("Failed to parse invalid as a mass value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_longest_run() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            longest_run(&data, &|a, b| a < b),
+            Run {
+                start: 0,
+                length: 4
+            }
+        );
+        assert_eq!(
+            longest_run(&data, &|a, b| a > b),
+            Run {
+                start: 3,
+                length: 2
+            }
+        );
+        assert_eq!(longest_run::<u32>(&[], &|a, b| a < b), Run::default());
+    }
+
+    #[test]
+    fn test_rolling_anomalies() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            rolling_anomalies(&data, 3, 20.0),
+            vec![
+                Anomaly {
+                    index: 6,
+                    depth: 240,
+                    average: 617.0 / 3.0
+                },
+                Anomaly {
+                    index: 7,
+                    depth: 269,
+                    average: 647.0 / 3.0
+                },
+                Anomaly {
+                    index: 8,
+                    depth: 260,
+                    average: 716.0 / 3.0
+                },
+            ]
+        );
+        assert_eq!(rolling_anomalies(&data, 0, 20.0), vec![]);
+        assert_eq!(rolling_anomalies(&data, 20, 0.0), vec![]);
+    }
+
+    #[test]
+    fn test_depth_report() {
+        let data = parse(EXAMPLE).unwrap();
+        let report = DepthReport::new(&data, 3, 20.0, 0.0).unwrap();
+
+        assert_eq!(report.min, 199);
+        assert_eq!(report.max, 269);
+        assert_eq!(report.mean, 225.6);
+        assert_eq!(
+            report.largest_rise,
+            Some(Jump {
+                index: 6,
+                from: 207,
+                to: 240
+            })
+        );
+        assert_eq!(
+            report.largest_fall,
+            Some(Jump {
+                index: 4,
+                from: 210,
+                to: 200
+            })
+        );
+        assert_eq!(report.anomalies.len(), 3);
+        assert_eq!(
+            report.longest_increase,
+            Run {
+                start: 0,
+                length: 4
+            }
+        );
+
+        // small steps no longer extend a run once they are within the tolerance
+        let report = DepthReport::new(&data, 3, 20.0, 5.0).unwrap();
+        assert_eq!(
+            report.longest_increase,
+            Run {
+                start: 4,
+                length: 4
+            }
+        );
+        assert_eq!(
+            report.longest_decrease,
+            Run {
+                start: 3,
+                length: 2
+            }
+        );
+
+        assert_eq!(DepthReport::<u32>::new(&[], 3, 20.0, 0.0), None);
+        assert_eq!(
+            DepthReport::new(&[5u32], 3, 20.0, 0.0)
+                .unwrap()
+                .largest_rise,
+            None
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let text = report(EXAMPLE, &Options::default(), 20.0).unwrap();
+        assert!(text.starts_with("Minimum depth: 199\nMaximum depth: 269\nMean depth: 225.60\n"));
+        assert!(text.contains("Largest rise: 207 -> 240 (+33) at reading 7"));
+        assert!(text.contains("Largest fall: 210 -> 200 (-10) at reading 5"));
+        assert!(text.ends_with("reading 9: 260 against rolling average 238.67"));
+
+        assert_eq!(
+            report("", &Options::default(), 20.0),
+            Err("Unable to report on empty depth data".to_string())
+        );
+    }
+
     #[test]
     fn test_solutions() {
         assert_eq!(solutions(EXAMPLE), Ok(("7".to_string(), "5".to_string())));
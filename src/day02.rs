@@ -19,6 +19,56 @@ along with this program.  If not, see <https://www.gnu.org/licenses/>.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Day02Error {
     ParseFailure(String),
+    Overflow(Direction),
+    ProductOverflow(i64, i64),
+    UnclosedBlock(usize),
+    UnmatchedBlockEnd(usize),
+    Unreachable(i64, i64),
+    TargetOutOfRange(i64, i64),
+    TrajectoryTooLong(usize),
+}
+
+impl std::fmt::Display for Day02Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day02Error::ParseFailure(d) => {
+                write!(f, "Failed to parse {} as a direction value", d)
+            }
+            Day02Error::Overflow(d) => {
+                write!(f, "Moving the submarine {} overflowed its position", d)
+            }
+            Day02Error::ProductOverflow(x, y) => {
+                write!(f, "Multiplying position {} by depth {} overflowed", x, y)
+            }
+            Day02Error::UnclosedBlock(line) => {
+                write!(
+                    f,
+                    "The repeat block opened on line {} is never closed",
+                    line
+                )
+            }
+            Day02Error::UnmatchedBlockEnd(line) => {
+                write!(f, "Line {} closes a block that was never opened", line)
+            }
+            Day02Error::Unreachable(x, depth) => {
+                write!(
+                    f,
+                    "Position {} at depth {} cannot be reached without backing up",
+                    x, depth
+                )
+            }
+            Day02Error::TargetOutOfRange(x, depth) => {
+                write!(
+                    f,
+                    "Position {} at depth {} is too far away to plan a course to",
+                    x, depth
+                )
+            }
+            Day02Error::TrajectoryTooLong(limit) => {
+                write!(f, "The plan runs more than {} commands to record", limit)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,112 +76,774 @@ pub enum Direction {
     Up(u32),
     Down(u32),
     Forward(u32),
+    Back(u32),
+    ResetAim,
 }
 
-pub trait Submarine {
-    fn move_in_direction(&self, direction: Direction) -> Self;
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Direction::Up(v) => write!(f, "up {}", v),
+            Direction::Down(v) => write!(f, "down {}", v),
+            Direction::Forward(v) => write!(f, "forward {}", v),
+            Direction::Back(v) => write!(f, "back {}", v),
+            Direction::ResetAim => write!(f, "reset aim"),
+        }
+    }
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubmarineState {
+    pub position: i64,
+    pub depth: i64,
+    pub aim: i64,
+    pub lateral: i64,
+}
+
+pub trait Submarine: Sized {
+    fn move_in_direction(&self, direction: Direction) -> Result<Self, Day02Error>;
+    fn state(&self) -> SubmarineState;
+}
+
+// Depths are measured downwards from the starting position, so surfacing above it gives
+// a negative depth rather than an underflow.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Solution1Submarine {
-    x: u32,
-    y: u32,
+    x: i64,
+    y: i64,
 }
 
 impl Submarine for Solution1Submarine {
-    fn move_in_direction(&self, direction: Direction) -> Self {
+    fn move_in_direction(&self, direction: Direction) -> Result<Self, Day02Error> {
         let mut submarine = *self;
+        let overflow = Day02Error::Overflow(direction);
+
         match direction {
             Direction::Up(v) => {
-                submarine.y -= v;
+                submarine.y = submarine.y.checked_sub(v as i64).ok_or(overflow)?;
             }
             Direction::Down(v) => {
-                submarine.y += v;
+                submarine.y = submarine.y.checked_add(v as i64).ok_or(overflow)?;
             }
             Direction::Forward(v) => {
-                submarine.x += v;
+                submarine.x = submarine.x.checked_add(v as i64).ok_or(overflow)?;
             }
+            Direction::Back(v) => {
+                submarine.x = submarine.x.checked_sub(v as i64).ok_or(overflow)?;
+            }
+            // this submarine has no aim to reset
+            Direction::ResetAim => {}
         }
 
-        return submarine;
+        return Ok(submarine);
+    }
+
+    fn state(&self) -> SubmarineState {
+        SubmarineState {
+            position: self.x,
+            depth: self.y,
+            aim: 0,
+            lateral: 0,
+        }
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Solution2Submarine {
-    x: u32,
-    y: u32,
-    aim: u32,
+    x: i64,
+    y: i64,
+    aim: i64,
 }
 
 impl Submarine for Solution2Submarine {
-    fn move_in_direction(&self, direction: Direction) -> Self {
+    fn move_in_direction(&self, direction: Direction) -> Result<Self, Day02Error> {
         let mut submarine = *self;
+        let overflow = Day02Error::Overflow(direction);
 
         match direction {
             Direction::Up(v) => {
-                submarine.aim -= v;
+                submarine.aim = submarine.aim.checked_sub(v as i64).ok_or(overflow)?;
             }
             Direction::Down(v) => {
-                submarine.aim += v;
+                submarine.aim = submarine.aim.checked_add(v as i64).ok_or(overflow)?;
             }
             Direction::Forward(v) => {
-                submarine.x += v;
-                submarine.y += v * submarine.aim;
+                submarine.x = submarine.x.checked_add(v as i64).ok_or(overflow.clone())?;
+                submarine.y = (v as i64)
+                    .checked_mul(submarine.aim)
+                    .and_then(|dy| submarine.y.checked_add(dy))
+                    .ok_or(overflow)?;
+            }
+            // backing up retraces a forward move, rising as much as going forward would dive
+            Direction::Back(v) => {
+                submarine.x = submarine.x.checked_sub(v as i64).ok_or(overflow.clone())?;
+                submarine.y = (v as i64)
+                    .checked_mul(submarine.aim)
+                    .and_then(|dy| submarine.y.checked_sub(dy))
+                    .ok_or(overflow)?;
+            }
+            Direction::ResetAim => {
+                submarine.aim = 0;
+            }
+        }
+
+        return Ok(submarine);
+    }
+
+    fn state(&self) -> SubmarineState {
+        SubmarineState {
+            position: self.x,
+            depth: self.y,
+            aim: self.aim,
+            lateral: 0,
+        }
+    }
+}
+
+pub const DEFAULT_CURRENT: i64 = 1;
+pub const DEFAULT_MAX_DEPTH: i64 = 1000;
+
+// Follows the aim rules of solution 2 while a steady cross-current pushes the submarine
+// sideways for as long as it is moving, whichever way it is heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriftSubmarine {
+    course: Solution2Submarine,
+    lateral: i64,
+    current: i64,
+}
+
+impl Default for DriftSubmarine {
+    fn default() -> Self {
+        DriftSubmarine {
+            course: Solution2Submarine::default(),
+            lateral: 0,
+            current: DEFAULT_CURRENT,
+        }
+    }
+}
+
+impl Submarine for DriftSubmarine {
+    fn move_in_direction(&self, direction: Direction) -> Result<Self, Day02Error> {
+        let mut submarine = *self;
+        submarine.course = self.course.move_in_direction(direction)?;
+
+        if let Direction::Forward(v) | Direction::Back(v) = direction {
+            submarine.lateral = (v as i64)
+                .checked_mul(submarine.current)
+                .and_then(|dz| submarine.lateral.checked_add(dz))
+                .ok_or(Day02Error::Overflow(direction))?;
+        }
+
+        return Ok(submarine);
+    }
+
+    fn state(&self) -> SubmarineState {
+        SubmarineState {
+            lateral: self.lateral,
+            ..self.course.state()
+        }
+    }
+}
+
+// Follows the aim rules of solution 2, but the hull cannot go any deeper than
+// `max_depth` so dives level off at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClampedSubmarine {
+    course: Solution2Submarine,
+    max_depth: i64,
+}
+
+impl Default for ClampedSubmarine {
+    fn default() -> Self {
+        ClampedSubmarine {
+            course: Solution2Submarine::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl Submarine for ClampedSubmarine {
+    fn move_in_direction(&self, direction: Direction) -> Result<Self, Day02Error> {
+        let mut submarine = *self;
+        submarine.course = self.course.move_in_direction(direction)?;
+        submarine.course.y = submarine.course.y.min(submarine.max_depth);
+
+        return Ok(submarine);
+    }
+
+    fn state(&self) -> SubmarineState {
+        self.course.state()
+    }
+}
+
+// The first point of a trajectory is the submarine before any command is followed, so it
+// has no command index or direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrajectoryPoint {
+    pub command: Option<usize>,
+    pub direction: Option<Direction>,
+    pub state: SubmarineState,
+}
+
+pub const MAX_TRAJECTORY_POINTS: usize = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    points: Vec<TrajectoryPoint>,
+}
+
+impl Trajectory {
+    // Records at most MAX_TRAJECTORY_POINTS commands, as plans with nested repeats can run
+    // far more commands than fit in memory.
+    pub fn record<S: Submarine, I: IntoIterator<Item = Direction>>(
+        start: S,
+        directions: I,
+    ) -> Result<Self, Day02Error> {
+        let mut points = vec![TrajectoryPoint {
+            command: None,
+            direction: None,
+            state: start.state(),
+        }];
+
+        let mut submarine = start;
+        for (i, direction) in directions.into_iter().enumerate() {
+            if i == MAX_TRAJECTORY_POINTS {
+                return Err(Day02Error::TrajectoryTooLong(MAX_TRAJECTORY_POINTS));
+            }
+
+            submarine = submarine.move_in_direction(direction)?;
+            points.push(TrajectoryPoint {
+                command: Some(i),
+                direction: Some(direction),
+                state: submarine.state(),
+            });
+        }
+
+        return Ok(Trajectory { points });
+    }
+
+    pub fn points(&self) -> &[TrajectoryPoint] {
+        &self.points
+    }
+
+    pub fn last(&self) -> &TrajectoryPoint {
+        &self.points[self.points.len() - 1]
+    }
+
+    // the earliest point at the deepest depth reached
+    pub fn max_depth(&self) -> &TrajectoryPoint {
+        self.points.iter().fold(&self.points[0], |deepest, p| {
+            if p.state.depth > deepest.state.depth {
+                p
+            } else {
+                deepest
             }
+        })
+    }
+
+    pub fn first_past_depth(&self, depth: i64) -> Option<&TrajectoryPoint> {
+        self.points.iter().find(|p| p.state.depth > depth)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "command,direction,position,depth,aim,lateral".to_string();
+        for point in self.points().iter() {
+            csv.push_str(&format!(
+                "\n{},{},{},{},{},{}",
+                point.command.map(|c| c.to_string()).unwrap_or_default(),
+                point.direction.map(|d| d.to_string()).unwrap_or_default(),
+                point.state.position,
+                point.state.depth,
+                point.state.aim,
+                point.state.lateral
+            ));
         }
 
-        return submarine;
+        csv
+    }
+}
+
+impl std::fmt::Display for Trajectory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let describe = |point: &TrajectoryPoint| match point.command {
+            Some(c) => format!("after command {}", c),
+            None => "at the start".to_string(),
+        };
+
+        let last = self.last();
+        writeln!(
+            f,
+            "Final position: {}, depth: {}, aim: {}",
+            last.state.position, last.state.depth, last.state.aim
+        )?;
+
+        let deepest = self.max_depth();
+        write!(
+            f,
+            "Maximum depth: {} {}",
+            deepest.state.depth,
+            describe(deepest)
+        )?;
+
+        match self.first_past_depth(self.points[0].state.depth) {
+            Some(point) => write!(
+                f,
+                "\nFirst dive below the starting depth: {}",
+                describe(point)
+            ),
+            None => write!(f, "\nFirst dive below the starting depth: never"),
+        }
+    }
+}
+
+const SVG_PANEL_WIDTH: f64 = 400.0;
+const SVG_PANEL_HEIGHT: f64 = 300.0;
+const SVG_MARGIN: f64 = 40.0;
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Scales a trajectory into a panel starting at `offset` on the x axis. SVG y coordinates
+// grow downwards, so deeper points are naturally drawn lower in the panel.
+fn svg_panel(name: &str, trajectory: &Trajectory, offset: f64) -> String {
+    let points = trajectory.points();
+    let bounds = |f: fn(&TrajectoryPoint) -> i64| {
+        let min = points.iter().map(f).min().unwrap_or(0);
+        let max = points.iter().map(f).max().unwrap_or(0);
+        (min as f64, (max - min).max(1) as f64)
+    };
+    let (min_x, span_x) = bounds(|p| p.state.position);
+    let (min_y, span_y) = bounds(|p| p.state.depth);
+
+    let project = |point: &TrajectoryPoint| {
+        let x = (point.state.position as f64 - min_x) / span_x;
+        let y = (point.state.depth as f64 - min_y) / span_y;
+        (
+            offset + SVG_MARGIN + x * (SVG_PANEL_WIDTH - 2.0 * SVG_MARGIN),
+            SVG_MARGIN + y * (SVG_PANEL_HEIGHT - 2.0 * SVG_MARGIN),
+        )
+    };
+
+    let path = points
+        .iter()
+        .map(|p| {
+            let (x, y) = project(p);
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut panel = format!(
+        "<g>\n<text x=\"{:.1}\" y=\"{:.1}\" font-weight=\"bold\">{}</text>\n",
+        offset + SVG_MARGIN,
+        SVG_MARGIN / 2.0,
+        escape_xml(name)
+    );
+    panel.push_str(&format!(
+        "<polyline fill=\"none\" stroke=\"steelblue\" points=\"{}\"/>\n",
+        path
+    ));
+
+    let deepest = trajectory.max_depth();
+    let annotations = [
+        ("start", "green", &points[0]),
+        ("end", "red", trajectory.last()),
+        ("deepest", "navy", deepest),
+    ];
+    for (label, colour, point) in annotations {
+        let (x, y) = project(point);
+        panel.push_str(&format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"{}\"/>\n",
+            x, y, colour
+        ));
+        panel.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\">{} ({}, {})</text>\n",
+            x + 6.0,
+            y - 6.0,
+            label,
+            point.state.position,
+            point.state.depth
+        ));
+    }
+    panel.push_str("</g>\n");
+
+    panel
+}
+
+// Draws each named trajectory in its own panel, side by side from left to right.
+pub fn render_svg(trajectories: &[(&str, &Trajectory)]) -> String {
+    let width = SVG_PANEL_WIDTH * trajectories.len() as f64;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        width, SVG_PANEL_HEIGHT
+    );
+
+    for (i, (name, trajectory)) in trajectories.iter().enumerate() {
+        svg.push_str(&svg_panel(name, trajectory, i as f64 * SVG_PANEL_WIDTH));
+    }
+    svg.push_str("</svg>");
+
+    svg
+}
+
+pub fn position_product(x: i64, y: i64) -> Result<i64, Day02Error> {
+    x.checked_mul(y).ok_or(Day02Error::ProductOverflow(x, y))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Move(Direction),
+    Repeat(u32, Vec<Command>),
+}
+
+// A parsed mission plan. Repeat blocks are kept as they are written, so running a plan
+// never needs to expand them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Program {
+    commands: Vec<Command>,
+}
+
+// Deeply nested repeat blocks are taken apart one level at a time, as dropping them
+// recursively can overflow the stack.
+impl Drop for Command {
+    fn drop(&mut self) {
+        if let Command::Repeat(_, body) = self {
+            let mut pending = std::mem::take(body);
+            while let Some(mut command) = pending.pop() {
+                if let Command::Repeat(_, inner) = &mut command {
+                    pending.append(inner);
+                }
+            }
+        }
+    }
+}
+
+// Walks the moves of a plan in order, keeping a frame of the commands, the next command
+// and the repetitions left for each open repeat block instead of expanding them.
+pub struct Directions<'a> {
+    frames: Vec<(&'a [Command], usize, u32)>,
+}
+
+impl<'a> Iterator for Directions<'a> {
+    type Item = Direction;
+
+    fn next(&mut self) -> Option<Direction> {
+        loop {
+            let (commands, index, remaining) = self.frames.last_mut()?;
+            let commands: &'a [Command] = commands;
+
+            if *index == commands.len() {
+                if *remaining > 1 {
+                    *remaining -= 1;
+                    *index = 0;
+                } else {
+                    self.frames.pop();
+                }
+                continue;
+            }
+
+            let command = &commands[*index];
+            *index += 1;
+            match command {
+                Command::Move(direction) => return Some(*direction),
+                Command::Repeat(0, _) => {}
+                Command::Repeat(count, body) => self.frames.push((body, 0, *count)),
+            }
+        }
+    }
+}
+
+impl Program {
+    pub fn run<S: Submarine>(&self, start: S) -> Result<S, Day02Error> {
+        self.directions()
+            .try_fold(start, |s, direction| s.move_in_direction(direction))
+    }
+
+    pub fn directions(&self) -> Directions<'_> {
+        Directions {
+            frames: vec![(&self.commands, 0, 1)],
+        }
     }
 }
 
 pub fn parse_direction(line: &str) -> Result<Direction, Day02Error> {
-    let data = line.split(" ").collect::<Vec<&str>>();
+    let data = line.split_whitespace().collect::<Vec<&str>>();
+
+    if data == ["reset", "aim"] {
+        return Ok(Direction::ResetAim);
+    }
 
     let mag = data
         .get(1)
+        .filter(|_| data.len() == 2)
         .and_then(|s| s.parse::<u32>().ok())
         .ok_or(Day02Error::ParseFailure(line.to_string()))?;
 
-    match data.get(0).map(|&s| s) {
+    match data.first().copied() {
         Some("forward") => Ok(Direction::Forward(mag)),
         Some("up") => Ok(Direction::Up(mag)),
         Some("down") => Ok(Direction::Down(mag)),
+        Some("back") => Ok(Direction::Back(mag)),
         _ => Err(Day02Error::ParseFailure(line.to_string())),
     }
 }
 
-pub fn parse(data: &str) -> Result<Vec<Direction>, Day02Error> {
-    data.lines()
-        .map(parse_direction)
-        .collect::<Result<Vec<Direction>, Day02Error>>()
+// Parses the count out of a `repeat N {` line, or returns None for any other line.
+pub fn parse_repeat(line: &str) -> Result<Option<u32>, Day02Error> {
+    let count = match line.strip_prefix("repeat") {
+        Some(rest) => rest,
+        None => return Ok(None),
+    };
+
+    return count
+        .trim()
+        .strip_suffix('{')
+        .and_then(|c| c.trim().parse::<u32>().ok())
+        .map(Some)
+        .ok_or(Day02Error::ParseFailure(line.to_string()));
 }
 
-pub fn solution1(directions: &Vec<Direction>) -> String {
-    let submarine = directions
-        .iter()
-        .fold(Solution1Submarine::default(), |submarine, &d| {
-            submarine.move_in_direction(d)
-        });
+pub fn parse(data: &str) -> Result<Program, Day02Error> {
+    // each open block remembers the line it was opened on, its count and the commands
+    // of the enclosing block
+    let mut blocks: Vec<(usize, u32, Vec<Command>)> = Vec::new();
+    let mut commands = Vec::new();
+
+    for (i, raw) in data.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
 
-    (submarine.x * submarine.y).to_string()
+        if let Some(count) = parse_repeat(line)? {
+            blocks.push((i + 1, count, std::mem::take(&mut commands)));
+        } else if line == "}" {
+            let (_, count, enclosing) = blocks.pop().ok_or(Day02Error::UnmatchedBlockEnd(i + 1))?;
+            let body = std::mem::replace(&mut commands, enclosing);
+            commands.push(Command::Repeat(count, body));
+        } else {
+            commands.push(Command::Move(parse_direction(line)?));
+        }
+    }
+
+    if let Some((line, _, _)) = blocks.last() {
+        return Err(Day02Error::UnclosedBlock(*line));
+    }
+
+    return Ok(Program { commands });
 }
 
-pub fn solution2(directions: &Vec<Direction>) -> String {
-    let submarine = directions
-        .iter()
-        .fold(Solution2Submarine::default(), |submarine, &d| {
-            submarine.move_in_direction(d)
-        });
+impl std::fmt::Display for SubmarineState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "position {}, depth {}, aim {}, lateral {}",
+            self.position, self.depth, self.aim, self.lateral
+        )
+    }
+}
+
+// Submarine models are registered by name so a course can be run under any of them.
+// Each model runs a program from its default starting state.
+pub struct SubmarineModel {
+    pub name: &'static str,
+    run: fn(&Program) -> Result<SubmarineState, Day02Error>,
+}
 
-    (submarine.x * submarine.y).to_string()
+fn run_model<S: Submarine + Default>(program: &Program) -> Result<SubmarineState, Day02Error> {
+    program.run(S::default()).map(|s| s.state())
+}
+
+impl SubmarineModel {
+    pub fn run(&self, program: &Program) -> Result<SubmarineState, Day02Error> {
+        (self.run)(program)
+    }
+}
+
+pub const MODELS: [SubmarineModel; 4] = [
+    SubmarineModel {
+        name: "solution1",
+        run: run_model::<Solution1Submarine>,
+    },
+    SubmarineModel {
+        name: "solution2",
+        run: run_model::<Solution2Submarine>,
+    },
+    SubmarineModel {
+        name: "drift",
+        run: run_model::<DriftSubmarine>,
+    },
+    SubmarineModel {
+        name: "clamped",
+        run: run_model::<ClampedSubmarine>,
+    },
+];
+
+pub fn model(name: &str) -> Option<&'static SubmarineModel> {
+    MODELS.iter().find(|m| m.name == name)
+}
+
+pub fn solution1(program: &Program) -> Result<String, Day02Error> {
+    let submarine = program.run(Solution1Submarine::default())?;
+
+    return position_product(submarine.x, submarine.y).map(|p| p.to_string());
+}
+
+pub fn solution2(program: &Program) -> Result<String, Day02Error> {
+    let submarine = program.run(Solution2Submarine::default())?;
+
+    return position_product(submarine.x, submarine.y).map(|p| p.to_string());
 }
 
 pub fn solutions(data: &str) -> Result<(String, String), String> {
-    let directions = parse(data).map_err(|Day02Error::ParseFailure(d)| {
-        format!("Failed to parse {} as a direction value", d)
-    })?;
+    let program = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&program).map_err(|e| e.to_string())?;
+    let soln2 = solution2(&program).map_err(|e| e.to_string())?;
+
+    return Ok((soln1, soln2));
+}
+
+// Runs the course under every named model, where "all" names every registered model. A
+// model that fails reports its error without stopping the others.
+pub fn compare_models(data: &str, names: &[&str]) -> Result<String, String> {
+    let program = parse(data).map_err(|e| e.to_string())?;
+
+    let mut models = Vec::new();
+    for &name in names.iter() {
+        if name == "all" {
+            models.extend(MODELS.iter());
+            continue;
+        }
+
+        let m = model(name).ok_or(format!(
+            "Unknown submarine model {}, expected one of {} or all",
+            name,
+            MODELS
+                .iter()
+                .map(|m| m.name)
+                .collect::<Vec<&str>>()
+                .join(", ")
+        ))?;
+        models.push(m);
+    }
+
+    let lines = models
+        .iter()
+        .map(|m| match m.run(&program) {
+            Ok(state) => format!("{}: {}", m.name, state),
+            Err(e) => format!("{}: {}", m.name, e),
+        })
+        .collect::<Vec<String>>();
+
+    return Ok(lines.join("\n"));
+}
+
+fn aim_command(aim: i64) -> Direction {
+    if aim < 0 {
+        return Direction::Up(aim.unsigned_abs() as u32);
+    }
+
+    Direction::Down(aim as u32)
+}
+
+fn move_command(distance: i64) -> Direction {
+    if distance < 0 {
+        return Direction::Back(distance.unsigned_abs() as u32);
+    }
+
+    Direction::Forward(distance as u32)
+}
+
+// Plans the shortest course taking a solution 2 submarine from the origin to the target.
+// Within the range of a single command every target needs at most three commands:
+//   - a single move when the target is at the starting depth,
+//   - an aim and a move when the depth is a multiple of the position,
+//   - otherwise a move to one step short of the position, then a final unit step with
+//     the aim set to the whole depth.
+// Without backing up the submarine can never return to or behind its starting position.
+pub fn plan_course(x: i64, depth: i64, allow_back: bool) -> Result<Vec<Direction>, Day02Error> {
+    let limit = u32::MAX as u64;
+    if x.unsigned_abs() > limit || depth.unsigned_abs() > limit {
+        return Err(Day02Error::TargetOutOfRange(x, depth));
+    }
+
+    if !allow_back && (x < 0 || (x == 0 && depth != 0)) {
+        return Err(Day02Error::Unreachable(x, depth));
+    }
+
+    if depth == 0 {
+        if x == 0 {
+            return Ok(Vec::new());
+        }
+        return Ok(vec![move_command(x)]);
+    }
+
+    if x != 0 && depth % x == 0 {
+        return Ok(vec![aim_command(depth / x), move_command(x)]);
+    }
+
+    let last = if x < 0 { -1 } else { 1 };
+    return Ok(vec![
+        move_command(x - last),
+        aim_command(depth * last),
+        move_command(last),
+    ]);
+}
+
+pub fn plan(x: i64, depth: i64, allow_back: bool) -> Result<String, String> {
+    let course = plan_course(x, depth, allow_back).map_err(|e| e.to_string())?;
+
+    let mut lines = vec![format!("# course to position {} at depth {}", x, depth)];
+    lines.extend(course.iter().map(|d| d.to_string()));
+
+    return Ok(lines.join("\n"));
+}
+
+// which puzzle part's submarine to follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Solution1,
+    Solution2,
+}
+
+pub fn trajectory(program: &Program, part: Part) -> Result<Trajectory, Day02Error> {
+    match part {
+        Part::Solution1 => Trajectory::record(Solution1Submarine::default(), program.directions()),
+        Part::Solution2 => Trajectory::record(Solution2Submarine::default(), program.directions()),
+    }
+}
+
+pub fn trajectory_csv(data: &str, part: Part) -> Result<String, String> {
+    let program = parse(data).map_err(|e| e.to_string())?;
+    let trajectory = trajectory(&program, part).map_err(|e| e.to_string())?;
+
+    return Ok(trajectory.to_csv());
+}
+
+pub fn svg(data: &str) -> Result<String, String> {
+    let program = parse(data).map_err(|e| e.to_string())?;
+    let trajectory1 = trajectory(&program, Part::Solution1).map_err(|e| e.to_string())?;
+    let trajectory2 = trajectory(&program, Part::Solution2).map_err(|e| e.to_string())?;
 
-    return Ok((solution1(&directions), solution2(&directions)));
+    return Ok(render_svg(&[
+        ("Solution 1 submarine", &trajectory1),
+        ("Solution 2 submarine", &trajectory2),
+    ]));
+}
+
+pub fn report(data: &str) -> Result<String, String> {
+    let program = parse(data).map_err(|e| e.to_string())?;
+    let trajectory1 = trajectory(&program, Part::Solution1).map_err(|e| e.to_string())?;
+    let trajectory2 = trajectory(&program, Part::Solution2).map_err(|e| e.to_string())?;
+
+    return Ok(format!(
+        "Solution 1 submarine\n{}\n\nSolution 2 submarine\n{}",
+        trajectory1, trajectory2
+    ));
 }
 
 #[cfg(test)]
@@ -148,12 +860,152 @@ mod tests {
             parse_direction("here? 5"),
             Err(Day02Error::ParseFailure("here? 5".to_string()))
         );
+        assert_eq!(parse_direction("back 3"), Ok(Direction::Back(3)));
+        assert_eq!(parse_direction("reset  aim"), Ok(Direction::ResetAim));
+        assert_eq!(
+            parse_direction("reset aim 2"),
+            Err(Day02Error::ParseFailure("reset aim 2".to_string()))
+        );
+        assert_eq!(
+            parse_direction("forward 5 5"),
+            Err(Day02Error::ParseFailure("forward 5 5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_repeat() {
+        assert_eq!(parse_repeat("repeat 3 {"), Ok(Some(3)));
+        assert_eq!(parse_repeat("repeat 12{"), Ok(Some(12)));
+        assert_eq!(parse_repeat("forward 3"), Ok(None));
+        assert_eq!(
+            parse_repeat("repeat 3"),
+            Err(Day02Error::ParseFailure("repeat 3".to_string()))
+        );
+        assert_eq!(
+            parse_repeat("repeat -1 {"),
+            Err(Day02Error::ParseFailure("repeat -1 {".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_program() {
+        let plan = "# dive, then sweep the trench\n\
+                    down 2\n\
+                    \n\
+                    repeat 2 {\n\
+                        forward 3 # along the floor\n\
+                        repeat 2 {\n\
+                            back 1\n\
+                        }\n\
+                    }\n\
+                    reset aim";
+
+        assert_eq!(
+            parse(plan),
+            Ok(Program {
+                commands: vec![
+                    Command::Move(Direction::Down(2)),
+                    Command::Repeat(
+                        2,
+                        vec![
+                            Command::Move(Direction::Forward(3)),
+                            Command::Repeat(2, vec![Command::Move(Direction::Back(1))])
+                        ]
+                    ),
+                    Command::Move(Direction::ResetAim)
+                ]
+            })
+        );
+        assert_eq!(
+            parse(plan).unwrap().directions().collect::<Vec<_>>(),
+            vec![
+                Direction::Down(2),
+                Direction::Forward(3),
+                Direction::Back(1),
+                Direction::Back(1),
+                Direction::Forward(3),
+                Direction::Back(1),
+                Direction::Back(1),
+                Direction::ResetAim
+            ]
+        );
+
+        assert_eq!(
+            parse("repeat 0 {\nforward 1\n}\nrepeat 2 {\n}\ndown 1")
+                .unwrap()
+                .directions()
+                .collect::<Vec<_>>(),
+            vec![Direction::Down(1)]
+        );
+
+        assert_eq!(parse(""), Ok(Program::default()));
+        assert_eq!(
+            parse("forward 1\nrepeat 2 {\nforward 1"),
+            Err(Day02Error::UnclosedBlock(2))
+        );
+        assert_eq!(
+            parse("forward 1\n}\nforward 1"),
+            Err(Day02Error::UnmatchedBlockEnd(2))
+        );
+        assert_eq!(
+            parse("repeat 2 {\nsideways 1\n}"),
+            Err(Day02Error::ParseFailure("sideways 1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_program_run() {
+        let program =
+            parse("down 2\nrepeat 3 {\nforward 2\n}\nback 1\nreset aim\nforward 4").unwrap();
+
+        assert_eq!(
+            program.run(Solution1Submarine::default()),
+            Ok(Solution1Submarine { x: 9, y: 2 })
+        );
+        assert_eq!(
+            program.run(Solution2Submarine::default()),
+            Ok(Solution2Submarine {
+                x: 9,
+                y: 10,
+                aim: 0
+            })
+        );
+
+        // deeply nested plans are run and dropped without recursing
+        let depth = 200_000;
+        let plan = format!(
+            "{}forward 1\n{}",
+            "repeat 1 {\n".repeat(depth),
+            "}\n".repeat(depth)
+        );
+        let program = parse(&plan).unwrap();
+        assert_eq!(
+            program.run(Solution1Submarine::default()),
+            Ok(Solution1Submarine { x: 1, y: 0 })
+        );
+        assert_eq!(
+            super::trajectory(&program, Part::Solution1).map(|t| t.last().state.position),
+            Ok(1)
+        );
+        drop(program);
+        assert!(parse(&plan[..plan.len() - 2]).is_err());
+
+        // a large repeat count is run without expanding the plan
+        let program = parse("down 1\nrepeat 4000000 {\nforward 1\n}").unwrap();
+        assert_eq!(
+            program.run(Solution2Submarine::default()),
+            Ok(Solution2Submarine {
+                x: 4000000,
+                y: 4000000,
+                aim: 1
+            })
+        );
     }
 
     #[test]
     fn test_parse() {
         assert_eq!(
-            parse(EXAMPLE),
+            parse(EXAMPLE).map(|p| p.directions().collect::<Vec<_>>()),
             Ok(vec![
                 Direction::Forward(5),
                 Direction::Down(5),
@@ -171,48 +1023,494 @@ mod tests {
 
     #[test]
     fn test_solution1_submarine_traitsubmarine() {
-        let sub = Solution1Submarine::default().move_in_direction(Direction::Down(1));
+        let sub = Solution1Submarine::default()
+            .move_in_direction(Direction::Down(1))
+            .unwrap();
 
         assert_eq!(
             sub.move_in_direction(Direction::Forward(1)),
-            Solution1Submarine { x: 1, y: 1 }
+            Ok(Solution1Submarine { x: 1, y: 1 })
         );
         assert_eq!(
             sub.move_in_direction(Direction::Up(1)),
-            Solution1Submarine { x: 0, y: 0 }
+            Ok(Solution1Submarine { x: 0, y: 0 })
         );
         assert_eq!(
             sub.move_in_direction(Direction::Down(1)),
-            Solution1Submarine { x: 0, y: 2 }
+            Ok(Solution1Submarine { x: 0, y: 2 })
+        );
+        assert_eq!(
+            sub.move_in_direction(Direction::Up(5)),
+            Ok(Solution1Submarine { x: 0, y: -4 })
+        );
+    }
+
+    #[test]
+    fn test_solution1_submarine_overflow() {
+        let sub = Solution1Submarine {
+            x: i64::MAX,
+            y: i64::MIN,
+        };
+
+        assert_eq!(
+            sub.move_in_direction(Direction::Forward(1)),
+            Err(Day02Error::Overflow(Direction::Forward(1)))
+        );
+        assert_eq!(
+            sub.move_in_direction(Direction::Up(1)),
+            Err(Day02Error::Overflow(Direction::Up(1)))
+        );
+        assert_eq!(
+            sub.move_in_direction(Direction::Down(1)),
+            Ok(Solution1Submarine {
+                x: i64::MAX,
+                y: i64::MIN + 1
+            })
         );
     }
 
     #[test]
     fn test_solution2_submarine_traitsubmarine() {
-        let sub = Solution2Submarine::default().move_in_direction(Direction::Down(1));
+        let sub = Solution2Submarine::default()
+            .move_in_direction(Direction::Down(1))
+            .unwrap();
 
         assert_eq!(
             sub.move_in_direction(Direction::Forward(1)),
-            Solution2Submarine { x: 1, y: 1, aim: 1 }
+            Ok(Solution2Submarine { x: 1, y: 1, aim: 1 })
         );
         assert_eq!(
             sub.move_in_direction(Direction::Up(1)),
-            Solution2Submarine { x: 0, y: 0, aim: 0 }
+            Ok(Solution2Submarine { x: 0, y: 0, aim: 0 })
         );
         assert_eq!(
             sub.move_in_direction(Direction::Down(1)),
-            Solution2Submarine { x: 0, y: 0, aim: 2 }
+            Ok(Solution2Submarine { x: 0, y: 0, aim: 2 })
+        );
+        assert_eq!(
+            sub.move_in_direction(Direction::Up(3))
+                .and_then(|s| s.move_in_direction(Direction::Forward(4))),
+            Ok(Solution2Submarine {
+                x: 4,
+                y: -8,
+                aim: -2
+            })
+        );
+    }
+
+    #[test]
+    fn test_solution2_submarine_overflow() {
+        let sub = Solution2Submarine {
+            x: 0,
+            y: i64::MAX - 1,
+            aim: 1,
+        };
+
+        assert_eq!(
+            sub.move_in_direction(Direction::Forward(1)),
+            Ok(Solution2Submarine {
+                x: 1,
+                y: i64::MAX,
+                aim: 1
+            })
+        );
+        assert_eq!(
+            sub.move_in_direction(Direction::Forward(2)),
+            Err(Day02Error::Overflow(Direction::Forward(2)))
+        );
+
+        let sub = Solution2Submarine {
+            x: 0,
+            y: 0,
+            aim: i64::MAX,
+        };
+        assert_eq!(
+            sub.move_in_direction(Direction::Forward(2)),
+            Err(Day02Error::Overflow(Direction::Forward(2)))
+        );
+    }
+
+    #[test]
+    fn test_drift_submarine() {
+        let program = parse("down 1\nforward 5\nback 2\nup 1").unwrap();
+        let sub = program.run(DriftSubmarine::default()).unwrap();
+
+        assert_eq!(
+            sub.state(),
+            SubmarineState {
+                position: 3,
+                depth: 3,
+                aim: 0,
+                lateral: 7
+            }
+        );
+
+        let sub = DriftSubmarine {
+            current: -2,
+            ..DriftSubmarine::default()
+        };
+        assert_eq!(
+            sub.move_in_direction(Direction::Forward(3))
+                .map(|s| s.state().lateral),
+            Ok(-6)
+        );
+
+        let sub = DriftSubmarine {
+            current: i64::MAX,
+            ..DriftSubmarine::default()
+        };
+        assert_eq!(
+            sub.move_in_direction(Direction::Back(2)),
+            Err(Day02Error::Overflow(Direction::Back(2)))
+        );
+    }
+
+    #[test]
+    fn test_clamped_submarine() {
+        let sub = ClampedSubmarine {
+            max_depth: 50,
+            ..ClampedSubmarine::default()
+        };
+        let sub = parse(EXAMPLE).unwrap().run(sub).unwrap();
+        assert_eq!(
+            sub.state(),
+            SubmarineState {
+                position: 15,
+                depth: 50,
+                aim: 10,
+                lateral: 0
+            }
+        );
+
+        // the clamp only limits how deep the submarine goes, it can still surface
+        let sub = sub.move_in_direction(Direction::Up(20)).unwrap();
+        let sub = sub.move_in_direction(Direction::Forward(1)).unwrap();
+        assert_eq!(sub.state().depth, 40);
+    }
+
+    #[test]
+    fn test_model() {
+        assert_eq!(model("drift").map(|m| m.name), Some("drift"));
+        assert!(model("sideways").is_none());
+
+        let program = parse(EXAMPLE).unwrap();
+        let states = MODELS
+            .iter()
+            .map(|m| m.run(&program).map(|s| (s.position, s.depth)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            vec![Ok((15, 10)), Ok((15, 60)), Ok((15, 60)), Ok((15, 60))]
+        );
+    }
+
+    #[test]
+    fn test_compare_models() {
+        assert_eq!(
+            compare_models(EXAMPLE, &["drift", "solution1"]),
+            Ok("drift: position 15, depth 60, aim 10, lateral 15\n\
+                solution1: position 15, depth 10, aim 0, lateral 0"
+                .to_string())
+        );
+        assert_eq!(
+            compare_models(EXAMPLE, &["all"]).map(|r| r.lines().count()),
+            Ok(MODELS.len())
+        );
+        assert_eq!(
+            compare_models(EXAMPLE, &["warp"]),
+            Err(
+                "Unknown submarine model warp, expected one of solution1, solution2, drift, clamped or all"
+                    .to_string()
+            )
+        );
+
+        // one model overflowing does not stop the others from reporting
+        assert_eq!(
+            compare_models(
+                "down 4294967295\nrepeat 3 {\nforward 4294967295\n}",
+                &["solution1", "solution2"]
+            ),
+            Ok(
+                "solution1: position 12884901885, depth 4294967295, aim 0, lateral 0\n\
+                solution2: Moving the submarine forward 4294967295 overflowed its position"
+                    .to_string()
+            )
+        );
+    }
+
+    fn follow_course(course: &[Direction]) -> (i64, i64) {
+        let sub = course
+            .iter()
+            .try_fold(Solution2Submarine::default(), |s, &d| {
+                s.move_in_direction(d)
+            })
+            .unwrap();
+
+        (sub.x, sub.y)
+    }
+
+    #[test]
+    fn test_plan_course() {
+        assert_eq!(plan_course(0, 0, false), Ok(vec![]));
+        assert_eq!(plan_course(7, 0, false), Ok(vec![Direction::Forward(7)]));
+        assert_eq!(
+            plan_course(15, 60, false),
+            Ok(vec![Direction::Down(4), Direction::Forward(15)])
+        );
+        assert_eq!(
+            plan_course(15, -61, false),
+            Ok(vec![
+                Direction::Forward(14),
+                Direction::Up(61),
+                Direction::Forward(1)
+            ])
+        );
+        assert_eq!(
+            plan_course(-4, 6, true),
+            Ok(vec![
+                Direction::Back(3),
+                Direction::Up(6),
+                Direction::Back(1)
+            ])
+        );
+        assert_eq!(
+            plan_course(0, 5, true),
+            Ok(vec![
+                Direction::Back(1),
+                Direction::Down(5),
+                Direction::Forward(1)
+            ])
+        );
+
+        assert_eq!(
+            plan_course(-4, 6, false),
+            Err(Day02Error::Unreachable(-4, 6))
+        );
+        assert_eq!(plan_course(0, 5, false), Err(Day02Error::Unreachable(0, 5)));
+        assert_eq!(
+            plan_course(i64::MIN, 0, true),
+            Err(Day02Error::TargetOutOfRange(i64::MIN, 0))
+        );
+    }
+
+    #[test]
+    fn test_plan_course_reaches_target() {
+        let max = u32::MAX as i64;
+        let targets = [
+            (1, 1),
+            (2, 3),
+            (12, -30),
+            (-1, -1),
+            (-6, 9),
+            (0, -8),
+            (max, max),
+            (-max, max - 1),
+            (max - 1, -max),
+        ];
+
+        for (x, depth) in targets {
+            let course = plan_course(x, depth, true).unwrap();
+            assert!(
+                course.len() <= 3,
+                "course to {:?}: {:?}",
+                (x, depth),
+                course
+            );
+            assert_eq!(follow_course(&course), (x, depth), "course {:?}", course);
+        }
+
+        // every course found without backing up only moves forward
+        for x in 0..10 {
+            for depth in -10..10 {
+                if let Ok(course) = plan_course(x, depth, false) {
+                    assert!(course.iter().all(|d| !matches!(d, Direction::Back(_))));
+                    assert_eq!(follow_course(&course), (x, depth));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let course = plan(5, 7, false).unwrap();
+        assert_eq!(
+            course,
+            "# course to position 5 at depth 7\nforward 4\ndown 7\nforward 1"
+        );
+        assert_eq!(solution2(&parse(&course).unwrap()), Ok("35".to_string()));
+
+        assert_eq!(
+            plan(-1, 0, false),
+            Err("Position -1 at depth 0 cannot be reached without backing up".to_string())
         );
     }
 
     #[test]
     fn test_solution1() {
-        assert_eq!(solution1(&parse(EXAMPLE).unwrap()), "150");
+        assert_eq!(solution1(&parse(EXAMPLE).unwrap()), Ok("150".to_string()));
+
+        // surfacing above the starting depth gives a negative depth
+        assert_eq!(
+            solution1(&parse("forward 4\nup 5\ndown 2").unwrap()),
+            Ok("-12".to_string())
+        );
     }
 
     #[test]
     fn test_solution2() {
-        assert_eq!(solution2(&parse(EXAMPLE).unwrap()), "900");
+        assert_eq!(solution2(&parse(EXAMPLE).unwrap()), Ok("900".to_string()));
+
+        assert_eq!(
+            solution2(&parse("up 5\nforward 4").unwrap()),
+            Ok("-80".to_string())
+        );
+    }
+
+    #[test]
+    fn test_position_product() {
+        assert_eq!(position_product(15, -10), Ok(-150));
+        assert_eq!(
+            position_product(i64::MAX, 2),
+            Err(Day02Error::ProductOverflow(i64::MAX, 2))
+        );
+    }
+
+    #[test]
+    fn test_trajectory_record() {
+        let directions = parse(EXAMPLE).unwrap().directions().collect::<Vec<_>>();
+        let trajectory =
+            Trajectory::record(Solution2Submarine::default(), directions.iter().copied()).unwrap();
+
+        assert_eq!(trajectory.points().len(), 7);
+        assert_eq!(
+            trajectory.points()[0],
+            TrajectoryPoint {
+                command: None,
+                direction: None,
+                state: SubmarineState::default()
+            }
+        );
+        assert_eq!(
+            trajectory.points()[3],
+            TrajectoryPoint {
+                command: Some(2),
+                direction: Some(Direction::Forward(8)),
+                state: SubmarineState {
+                    position: 13,
+                    depth: 40,
+                    aim: 5,
+                    lateral: 0
+                }
+            }
+        );
+        assert_eq!(
+            trajectory.last().state,
+            SubmarineState {
+                position: 15,
+                depth: 60,
+                aim: 10,
+                lateral: 0
+            }
+        );
+
+        let overflowing = Solution1Submarine { x: i64::MAX, y: 0 };
+        assert_eq!(
+            Trajectory::record(overflowing, directions.iter().copied()),
+            Err(Day02Error::Overflow(Direction::Forward(5)))
+        );
+
+        // nested repeats are walked lazily and stop once the trajectory is too long to keep
+        let program = parse("repeat 100000 {\nrepeat 100000 {\nforward 1\n}\n}").unwrap();
+        assert_eq!(
+            super::trajectory(&program, Part::Solution1),
+            Err(Day02Error::TrajectoryTooLong(MAX_TRAJECTORY_POINTS))
+        );
+    }
+
+    #[test]
+    fn test_trajectory_queries() {
+        let program = parse(EXAMPLE).unwrap();
+        let trajectory1 = trajectory(&program, Part::Solution1).unwrap();
+        let trajectory2 = trajectory(&program, Part::Solution2).unwrap();
+
+        assert_eq!(trajectory1.max_depth().command, Some(4));
+        assert_eq!(trajectory1.max_depth().state.depth, 10);
+        assert_eq!(trajectory2.max_depth().command, Some(5));
+        assert_eq!(trajectory2.max_depth().state.depth, 60);
+
+        assert_eq!(trajectory1.first_past_depth(0).unwrap().command, Some(1));
+        assert_eq!(trajectory1.first_past_depth(5).unwrap().command, Some(4));
+        assert_eq!(trajectory1.first_past_depth(10), None);
+        assert_eq!(trajectory2.first_past_depth(0).unwrap().command, Some(2));
+        assert_eq!(trajectory2.first_past_depth(-1).unwrap().command, None);
+
+        // a submarine that only surfaces stays deepest at the start
+        let surfacing = trajectory(&parse("up 3\nforward 2").unwrap(), Part::Solution1).unwrap();
+        assert_eq!(surfacing.max_depth().command, None);
+    }
+
+    #[test]
+    fn test_trajectory_to_csv() {
+        let program = parse("forward 5\ndown 5\nforward 8").unwrap();
+        assert_eq!(
+            trajectory(&program, Part::Solution2).unwrap().to_csv(),
+            "command,direction,position,depth,aim,lateral\n\
+             ,,0,0,0,0\n\
+             0,forward 5,5,0,0,0\n\
+             1,down 5,5,0,5,0\n\
+             2,forward 8,13,40,5,0"
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let program = parse("forward 2\ndown 2").unwrap();
+        let trajectory1 = trajectory(&program, Part::Solution1).unwrap();
+        let trajectory2 = trajectory(&program, Part::Solution2).unwrap();
+        let svg = render_svg(&[("one", &trajectory1), ("<two>", &trajectory2)]);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\""));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+
+        // depth increases downwards, and the second panel sits to the right of the first
+        assert!(svg.contains("points=\"40.0,40.0 360.0,40.0 360.0,260.0\""));
+        assert!(svg.contains("points=\"440.0,40.0 760.0,40.0 760.0,40.0\""));
+
+        assert!(svg.contains(">start (0, 0)</text>"));
+        assert!(svg.contains(">end (2, 2)</text>"));
+        assert!(svg.contains(">deepest (2, 2)</text>"));
+        assert!(svg.contains(">&lt;two&gt;</text>"));
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            report(EXAMPLE),
+            Ok("Solution 1 submarine\n\
+                Final position: 15, depth: 10, aim: 0\n\
+                Maximum depth: 10 after command 4\n\
+                First dive below the starting depth: after command 1\n\
+                \n\
+                Solution 2 submarine\n\
+                Final position: 15, depth: 60, aim: 10\n\
+                Maximum depth: 60 after command 5\n\
+                First dive below the starting depth: after command 2"
+                .to_string())
+        );
+        assert_eq!(
+            report("up 1"),
+            Ok("Solution 1 submarine\n\
+                Final position: 0, depth: -1, aim: 0\n\
+                Maximum depth: 0 at the start\n\
+                First dive below the starting depth: never\n\
+                \n\
+                Solution 2 submarine\n\
+                Final position: 0, depth: 0, aim: -1\n\
+                Maximum depth: 0 at the start\n\
+                First dive below the starting depth: never"
+                .to_string())
+        );
     }
 
     #[test]
@@ -225,5 +1523,9 @@ mod tests {
             solutions("invalidinput"),
             Err("Failed to parse invalidinput as a direction value".to_string())
         );
+        assert_eq!(
+            solutions("# surface first\nup 3\n\nrepeat 2 {\n  forward 1\n}"),
+            Ok(("-6".to_string(), "-12".to_string()))
+        );
     }
 }
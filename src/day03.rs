@@ -16,138 +16,736 @@ You should have received a copy of the GNU General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use crate::PartSolution;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Day03Error {
     EmptyData,
     ParseFailure(String),
-    NoOxygenRate,
-    NoCO2Rate,
+    NoOxygenRate(RatingFailure),
+    NoCO2Rate(RatingFailure),
+    InconsistentWidth(u32, Vec<(usize, u32)>),
+}
+
+// Why reducing a report under some bit criteria did not leave a single rating. Bits are
+// numbered from the right starting at 0, while columns are numbered from the left
+// starting at 1 as they appear in the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingFailure {
+    Emptied { bit: u32, column: u32 },
+    Ambiguous(usize),
+}
+
+impl std::fmt::Display for RatingFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RatingFailure::Emptied { bit, column } => {
+                write!(
+                    f,
+                    "no candidates were left after filtering on bit {} (column {})",
+                    bit, column
+                )
+            }
+            RatingFailure::Ambiguous(count) => {
+                write!(
+                    f,
+                    "{} identical candidates were left after filtering on every bit",
+                    count
+                )
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for Day03Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match &*self {
+        match self {
             Day03Error::ParseFailure(why) => {
                 write!(f, "Failed to parse {} as a binary number", why)
             }
             Day03Error::EmptyData => {
                 write!(f, "Failed to parse empty data")
             }
-            Day03Error::NoCO2Rate => {
-                write!(f, "Unable to find a CO2 rate")
+            Day03Error::NoCO2Rate(why) => {
+                write!(f, "Unable to find a CO2 rate: {}", why)
+            }
+            Day03Error::NoOxygenRate(why) => {
+                write!(f, "Unable to find an Oxygen rate: {}", why)
             }
-            Day03Error::NoOxygenRate => {
-                write!(f, "Unable to find an Oxygen rate")
+            Day03Error::InconsistentWidth(expected, lines) => {
+                write!(f, "Expected every line to be {} bits wide, but", expected)?;
+                for (i, (line, width)) in lines.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { "," };
+                    write!(f, "{} line {} is {} bits wide", separator, line, width)?;
+                }
+                Ok(())
             }
         }
     }
 }
 
-pub fn parse_binary(line: &str) -> Result<u32, Day03Error> {
-    u32::from_str_radix(line, 2)
-        .ok()
-        .ok_or(Day03Error::ParseFailure(line.to_string()))
+// Bits are packed into 64 bit words, least significant word first, so bit 0 is the last
+// character of the binary string a vector was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitVector {
+    words: Vec<u64>,
+    width: u32,
 }
 
-pub fn parse(data: &str) -> Result<(Vec<u32>, u32), Day03Error> {
-    let num_bits = data
-        .lines()
-        .next()
-        .map(|n| n.chars().count())
-        .ok_or(Day03Error::EmptyData)? as u32;
-    let numbers = data
-        .lines()
-        .map(parse_binary)
-        .collect::<Result<Vec<u32>, Day03Error>>()?;
+impl BitVector {
+    pub fn zeros(width: u32) -> Self {
+        BitVector {
+            words: vec![0; (width as usize).div_ceil(64)],
+            width,
+        }
+    }
 
-    return Ok((numbers, num_bits));
+    pub fn bit(&self, index: u32) -> u32 {
+        if index >= self.width {
+            return 0;
+        }
+
+        ((self.words[index as usize / 64] >> (index % 64)) & 1) as u32
+    }
+
+    // Widens the vector with leading zeros, which leaves its value unchanged.
+    pub fn pad_to(&mut self, width: u32) {
+        if width > self.width {
+            self.words.resize((width as usize).div_ceil(64), 0);
+            self.width = width;
+        }
+    }
+
+    pub fn set_bit(&mut self, index: u32, value: u32) {
+        let word = &mut self.words[index as usize / 64];
+        let mask = 1 << (index % 64);
+        if value == 0 {
+            *word &= !mask;
+        } else {
+            *word |= mask;
+        }
+    }
+
+    // Schoolbook multiplication, so the product of two rates never overflows however wide
+    // the report is.
+    pub fn multiply(&self, other: &BitVector) -> BitVector {
+        let mut product = BitVector::zeros(self.width + other.width);
+
+        for (i, &a) in self.words.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.words.iter().enumerate() {
+                let total = a as u128 * b as u128 + product.words[i + j] as u128 + carry;
+                product.words[i + j] = total as u64;
+                carry = total >> 64;
+            }
+
+            let mut k = i + other.words.len();
+            while carry > 0 {
+                let total = product.words[k] as u128 + carry;
+                product.words[k] = total as u64;
+                carry = total >> 64;
+                k += 1;
+            }
+        }
+
+        product
+    }
+}
+
+impl std::str::FromStr for BitVector {
+    type Err = Day03Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if line.is_empty() {
+            return Err(Day03Error::ParseFailure(line.to_string()));
+        }
+
+        let mut bits = BitVector::zeros(line.chars().count() as u32);
+        for (index, c) in line.chars().rev().enumerate() {
+            match c {
+                '0' => {}
+                '1' => bits.set_bit(index as u32, 1),
+                _ => return Err(Day03Error::ParseFailure(line.to_string())),
+            }
+        }
+
+        return Ok(bits);
+    }
+}
+
+// Writes the vector as a decimal number by repeatedly dividing it into base 10^19 digits.
+impl std::fmt::Display for BitVector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        const BASE: u128 = 10_000_000_000_000_000_000;
+
+        let mut words = self.words.clone();
+        let mut digits = Vec::new();
+        while words.iter().any(|&w| w != 0) {
+            let mut remainder = 0u128;
+            for word in words.iter_mut().rev() {
+                let current = (remainder << 64) | *word as u128;
+                *word = (current / BASE) as u64;
+                remainder = current % BASE;
+            }
+            digits.push(remainder as u64);
+        }
+
+        match digits.split_last() {
+            Some((most, rest)) => {
+                write!(f, "{}", most)?;
+                for digit in rest.iter().rev() {
+                    write!(f, "{:019}", digit)?;
+                }
+                Ok(())
+            }
+            None => write!(f, "0"),
+        }
+    }
+}
+
+pub fn parse_binary(line: &str) -> Result<BitVector, Day03Error> {
+    line.parse()
 }
 
-pub fn common_bit(diagnostic: &Vec<u32>, bit_index: u32) -> u32 {
-    let (zeros, ones): (Vec<u32>, Vec<u32>) = diagnostic
+// Every line must be as wide as the most common width, with ties going to the width seen
+// first, unless `pad` is set, in which case lines are left-padded with zeros to the width
+// of the widest line. Widths are checked before parsing, so blank lines are reported too.
+pub fn parse_with(data: &str, pad: bool) -> Result<(Vec<BitVector>, u32), Day03Error> {
+    let lines = data.lines().collect::<Vec<&str>>();
+    let widths = lines
         .iter()
-        .map(|&n| n & 1 << bit_index)
-        .partition(|&n| n == 0);
+        .map(|line| line.chars().count() as u32)
+        .collect::<Vec<u32>>();
+
+    // each width with the number of lines that have it, in the order they first appear
+    let mut counts: Vec<(u32, usize)> = Vec::new();
+    for &width in widths.iter() {
+        match counts.iter_mut().find(|(w, _)| *w == width) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((width, 1)),
+        }
+    }
+
+    let num_bits = if pad {
+        widths.iter().copied().max()
+    } else {
+        counts
+            .iter()
+            .copied()
+            .reduce(|best, c| if c.1 > best.1 { c } else { best })
+            .map(|(width, _)| width)
+    };
+    let num_bits = num_bits.filter(|&n| n > 0).ok_or(Day03Error::EmptyData)?;
+
+    let mismatched = widths
+        .iter()
+        .enumerate()
+        .filter(|(_, &width)| width != num_bits && !pad)
+        .map(|(i, &width)| (i + 1, width))
+        .collect::<Vec<(usize, u32)>>();
+    if !mismatched.is_empty() {
+        return Err(Day03Error::InconsistentWidth(num_bits, mismatched));
+    }
+
+    let mut numbers = Vec::with_capacity(lines.len());
+    for line in lines.iter() {
+        let mut number = match line.is_empty() {
+            true => BitVector::zeros(num_bits),
+            false => parse_binary(line)?,
+        };
+        number.pad_to(num_bits);
+        numbers.push(number);
+    }
+
+    return Ok((numbers, num_bits));
+}
+
+pub fn parse(data: &str) -> Result<(Vec<BitVector>, u32), Day03Error> {
+    parse_with(data, false)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Commonness {
+    Most,
+    Least,
+}
+
+// Chooses which bit value to keep at a position: the most or least common one, or 1 when
+// both are equally common and `ties_keep_one` is set, 0 otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitCriteria {
+    pub keep: Commonness,
+    pub ties_keep_one: bool,
+}
+
+impl BitCriteria {
+    pub const MOST_COMMON: BitCriteria = BitCriteria {
+        keep: Commonness::Most,
+        ties_keep_one: true,
+    };
+    pub const LEAST_COMMON: BitCriteria = BitCriteria {
+        keep: Commonness::Least,
+        ties_keep_one: false,
+    };
+
+    pub fn select(&self, zeros: usize, ones: usize) -> u32 {
+        if zeros == ones {
+            return self.ties_keep_one as u32;
+        }
+
+        let most = if zeros > ones { 0 } else { 1 };
+        match self.keep {
+            Commonness::Most => most,
+            Commonness::Least => most ^ 1,
+        }
+    }
+}
+
+// Parses `most` or `least`, optionally followed by the bit kept on ties, e.g. `least:1`.
+// Without a tie bit the puzzle's rules apply, keeping 1 for most and 0 for least.
+impl std::str::FromStr for BitCriteria {
+    type Err = String;
 
-    if zeros.len() > ones.len() {
-        return 0;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let failure = || {
+            format!(
+                "Unknown bit criteria {}, expected most or least with an optional tie bit such as least:1",
+                s
+            )
+        };
+
+        let (keep, tie) = match s.split_once(':') {
+            Some((keep, tie)) => (keep, Some(tie)),
+            None => (s, None),
+        };
+
+        let criteria = match keep {
+            "most" => BitCriteria::MOST_COMMON,
+            "least" => BitCriteria::LEAST_COMMON,
+            _ => return Err(failure()),
+        };
+
+        let ties_keep_one = match tie {
+            Some("0") => false,
+            Some("1") => true,
+            Some(_) => return Err(failure()),
+            None => criteria.ties_keep_one,
+        };
+
+        return Ok(BitCriteria {
+            ties_keep_one,
+            ..criteria
+        });
     }
+}
 
-    return 1;
+impl std::fmt::Display for BitCriteria {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let keep = match self.keep {
+            Commonness::Most => "most",
+            Commonness::Least => "least",
+        };
+
+        write!(
+            f,
+            "{} common, ties keep {}",
+            keep, self.ties_keep_one as u32
+        )
+    }
 }
 
-pub fn gamma_rate(diagnostic: &Vec<u32>, num_bits: u32) -> u32 {
-    (0..num_bits)
-        .map(|bit_index| common_bit(diagnostic, bit_index) * 1 << bit_index)
-        .sum()
+// the number of zeros and ones at a bit position across the report
+pub fn count_bits<B: std::borrow::Borrow<BitVector>>(
+    diagnostic: &[B],
+    bit_index: u32,
+) -> (usize, usize) {
+    let ones = diagnostic
+        .iter()
+        .filter(|n| n.borrow().bit(bit_index) == 1)
+        .count();
+
+    (diagnostic.len() - ones, ones)
 }
 
-pub fn epsilon_rate(diagnostic: &Vec<u32>, num_bits: u32) -> u32 {
-    (0..num_bits)
-        .map(|bit_index| (common_bit(diagnostic, bit_index) ^ 1) * 1 << bit_index)
-        .sum()
+pub fn select_bit(diagnostic: &[BitVector], bit_index: u32, criteria: &BitCriteria) -> u32 {
+    let (zeros, ones) = count_bits(diagnostic, bit_index);
+
+    criteria.select(zeros, ones)
+}
+
+pub fn common_bit(diagnostic: &[BitVector], bit_index: u32) -> u32 {
+    select_bit(diagnostic, bit_index, &BitCriteria::MOST_COMMON)
+}
+
+pub fn gamma_rate(diagnostic: &[BitVector], num_bits: u32) -> BitVector {
+    let mut rate = BitVector::zeros(num_bits);
+    for bit_index in 0..num_bits {
+        rate.set_bit(bit_index, common_bit(diagnostic, bit_index));
+    }
+
+    rate
 }
 
-pub fn solution1(diagnostic: &Vec<u32>, num_bits: u32) -> String {
-    let answer = gamma_rate(diagnostic, num_bits) * epsilon_rate(diagnostic, num_bits);
+pub fn epsilon_rate(diagnostic: &[BitVector], num_bits: u32) -> BitVector {
+    let mut rate = BitVector::zeros(num_bits);
+    for bit_index in 0..num_bits {
+        rate.set_bit(
+            bit_index,
+            select_bit(diagnostic, bit_index, &BitCriteria::LEAST_COMMON),
+        );
+    }
+
+    rate
+}
+
+pub fn solution1(diagnostic: &[BitVector], num_bits: u32) -> String {
+    let answer = gamma_rate(diagnostic, num_bits).multiply(&epsilon_rate(diagnostic, num_bits));
     return answer.to_string();
 }
 
-pub fn reduce_diagnostics(
-    diagnostic: &Vec<u32>,
+// One filtering round of a rating: the candidates' bits at `bit`, the bit kept and how
+// many candidates remain afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReductionRound {
+    pub bit: u32,
+    pub zeros: usize,
+    pub ones: usize,
+    pub kept: u32,
+    pub remaining: usize,
+}
+
+pub fn reduction_rounds(
+    diagnostic: &[BitVector],
     num_bits: u32,
-    criteria: &dyn Fn(u32, u32, u32) -> bool,
-) -> Option<u32> {
-    let mut numbers = diagnostic.clone();
+    criteria: &BitCriteria,
+) -> (Vec<ReductionRound>, Result<BitVector, RatingFailure>) {
+    // candidates are filtered by reference, so only the rating found is copied
+    let mut numbers = diagnostic.iter().collect::<Vec<&BitVector>>();
+    let mut rounds = Vec::new();
 
     for n in (0..num_bits).rev() {
-        let common = common_bit(&numbers, n);
-        numbers = numbers
-            .iter()
-            .filter(|&&num| criteria(num, n, common))
-            .map(|&x| x)
-            .collect::<Vec<u32>>();
+        let (zeros, ones) = count_bits(&numbers, n);
+        let kept = criteria.select(zeros, ones);
+        numbers.retain(|num| num.bit(n) == kept);
+
+        rounds.push(ReductionRound {
+            bit: n,
+            zeros,
+            ones,
+            kept,
+            remaining: numbers.len(),
+        });
+
+        match numbers.len() {
+            0 => {
+                let failure = RatingFailure::Emptied {
+                    bit: n,
+                    column: num_bits - n,
+                };
+                return (rounds, Err(failure));
+            }
+            1 => return (rounds, Ok(numbers[0].clone())),
+            _ => {}
+        }
+    }
+
+    let failure = RatingFailure::Ambiguous(numbers.len());
+    return (rounds, Err(failure));
+}
+
+pub fn reduce_diagnostics(
+    diagnostic: &[BitVector],
+    num_bits: u32,
+    criteria: &BitCriteria,
+) -> Result<BitVector, RatingFailure> {
+    reduction_rounds(diagnostic, num_bits, criteria).1
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TrieNode {
+    count: u32,
+    // the first number inserted below this node, which is the only one when count is 1
+    number: u32,
+    // index of the child for each bit value, 0 when there is no child
+    children: [u32; 2],
+}
+
+// A binary trie over the report, most significant bit first, with the number of report
+// lines below each node. A node holding a single line is left as a leaf instead of being
+// expanded to full depth, so the trie stays close to the size of the report however wide
+// the lines are. Every rating is a single walk from the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticTrie<'a> {
+    numbers: &'a [BitVector],
+    num_bits: u32,
+    nodes: Vec<TrieNode>,
+}
+
+impl<'a> DiagnosticTrie<'a> {
+    pub fn new(numbers: &'a [BitVector], num_bits: u32) -> Self {
+        let mut trie = DiagnosticTrie {
+            numbers,
+            num_bits,
+            nodes: vec![TrieNode::default()],
+        };
 
-        if numbers.len() == 1 {
-            return Some(numbers[0]);
+        for index in 0..numbers.len() {
+            trie.insert(index as u32);
         }
+
+        trie
     }
 
-    return None;
+    fn add_node(&mut self, count: u32, number: u32) -> u32 {
+        self.nodes.push(TrieNode {
+            count,
+            number,
+            children: [0, 0],
+        });
+
+        (self.nodes.len() - 1) as u32
+    }
+
+    fn insert(&mut self, index: u32) {
+        let mut node = 0;
+
+        for n in (0..self.num_bits).rev() {
+            self.nodes[node].count += 1;
+            match self.nodes[node].count {
+                1 => {
+                    self.nodes[node].number = index;
+                    return;
+                }
+                // the node was a leaf, so its line moves down a level to make room
+                2 => {
+                    let existing = self.nodes[node].number;
+                    let bit = self.numbers[existing as usize].bit(n) as usize;
+                    self.nodes[node].children[bit] = self.add_node(1, existing);
+                }
+                _ => {}
+            }
+
+            let bit = self.numbers[index as usize].bit(n) as usize;
+            node = match self.nodes[node].children[bit] {
+                0 => {
+                    let child = self.add_node(0, 0);
+                    self.nodes[node].children[bit] = child;
+                    child
+                }
+                child => child,
+            } as usize;
+        }
+
+        self.nodes[node].count += 1;
+        if self.nodes[node].count == 1 {
+            self.nodes[node].number = index;
+        }
+    }
+
+    // the nodes and counts of the lines below `node` with a 0 or a 1 at bit `n`
+    fn split(&self, node: usize, n: u32) -> [(usize, usize); 2] {
+        let current = &self.nodes[node];
+        if current.count == 1 {
+            let bit = self.numbers[current.number as usize].bit(n) as usize;
+            let mut split = [(node, 0), (node, 0)];
+            split[bit].1 = 1;
+            return split;
+        }
+
+        current.children.map(|child| {
+            let child = child as usize;
+            let count = if child == 0 {
+                0
+            } else {
+                self.nodes[child].count
+            };
+            (child, count as usize)
+        })
+    }
+
+    pub fn rating(&self, criteria: &BitCriteria) -> Result<&'a BitVector, RatingFailure> {
+        let mut node = 0;
+
+        for n in (0..self.num_bits).rev() {
+            let split = self.split(node, n);
+            let keep = criteria.select(split[0].1, split[1].1) as usize;
+            let (child, count) = split[keep];
+
+            match count {
+                0 => {
+                    return Err(RatingFailure::Emptied {
+                        bit: n,
+                        column: self.num_bits - n,
+                    })
+                }
+                1 => return Ok(&self.numbers[self.nodes[child].number as usize]),
+                _ => node = child,
+            }
+        }
+
+        return Err(RatingFailure::Ambiguous(self.nodes[node].count as usize));
+    }
+}
+
+pub fn oxygen_generator_rate<'a>(
+    trie: &DiagnosticTrie<'a>,
+) -> Result<&'a BitVector, RatingFailure> {
+    trie.rating(&BitCriteria::MOST_COMMON)
 }
 
-pub fn oxygen_generator_rate_criteria(num: u32, index: u32, common_bit: u32) -> bool {
-    (num & 1 << index) == common_bit << index
+pub fn co2_scrubber_rate<'a>(trie: &DiagnosticTrie<'a>) -> Result<&'a BitVector, RatingFailure> {
+    trie.rating(&BitCriteria::LEAST_COMMON)
 }
 
-pub fn oxygen_generator_rate(diagnostic: &Vec<u32>, num_bits: u32) -> Option<u32> {
-    reduce_diagnostics(diagnostic, num_bits, &oxygen_generator_rate_criteria)
+// The trie is only built when the report has many lines for each possible value, so that
+// lines share most of their nodes. Otherwise it grows to about two nodes per line and is
+// slower to build than filtering the report, which halves the candidates every round.
+const TRIE_LINES_PER_VALUE: usize = 64;
+
+fn use_trie(lines: usize, num_bits: u32) -> bool {
+    1usize
+        .checked_shl(num_bits)
+        .and_then(|values| values.checked_mul(TRIE_LINES_PER_VALUE))
+        .is_some_and(|needed| needed <= lines)
 }
 
-pub fn co2_scrubber_rate_criteria(num: u32, index: u32, common_bit: u32) -> bool {
-    (num & 1 << index) != common_bit << index
+pub fn solution2(diagnostic: &[BitVector], num_bits: u32) -> Result<String, Day03Error> {
+    let (oxy, co2) = if use_trie(diagnostic.len(), num_bits) {
+        let trie = DiagnosticTrie::new(diagnostic, num_bits);
+        (
+            oxygen_generator_rate(&trie).cloned(),
+            co2_scrubber_rate(&trie).cloned(),
+        )
+    } else {
+        (
+            reduce_diagnostics(diagnostic, num_bits, &BitCriteria::MOST_COMMON),
+            reduce_diagnostics(diagnostic, num_bits, &BitCriteria::LEAST_COMMON),
+        )
+    };
+    let oxy = oxy.map_err(Day03Error::NoOxygenRate)?;
+    let co2 = co2.map_err(Day03Error::NoCO2Rate)?;
+
+    return Ok(oxy.multiply(&co2).to_string());
 }
 
-pub fn co2_scrubber_rate(diagnostic: &Vec<u32>, num_bits: u32) -> Option<u32> {
-    reduce_diagnostics(diagnostic, num_bits, &co2_scrubber_rate_criteria)
+// Each part is solved independently, so part 1 is still answered when part 2 fails.
+fn solve(diagnostic: &[BitVector], num_bits: u32) -> (PartSolution, PartSolution) {
+    let soln1 = Ok(solution1(diagnostic, num_bits));
+    let soln2 = solution2(diagnostic, num_bits).map_err(|e| e.to_string());
+
+    return (soln1, soln2);
 }
 
-pub fn solution2(diagnostic: &Vec<u32>, num_bits: u32) -> Result<String, Day03Error> {
-    let oxy = oxygen_generator_rate(diagnostic, num_bits).ok_or(Day03Error::NoOxygenRate)?;
-    let co2 = co2_scrubber_rate(diagnostic, num_bits).ok_or(Day03Error::NoCO2Rate)?;
+pub fn solutions_with(data: &str, pad: bool) -> Result<(PartSolution, PartSolution), String> {
+    let (diagnostic, num_bits) = parse_with(data, pad).map_err(|e| e.to_string())?;
 
-    return Ok((oxy * co2).to_string());
+    return Ok(solve(&diagnostic, num_bits));
 }
 
-pub fn solutions(data: &str) -> Result<(String, String), String> {
+pub fn solutions(data: &str) -> Result<(PartSolution, PartSolution), String> {
     let (diagnostic, num_bits) = parse(data).map_err(|e| e.to_string())?;
-    let soln1 = solution1(&diagnostic, num_bits);
-    let soln2 = match solution2(&diagnostic, num_bits) {
-        Ok(ans) => ans,
-        Err(why) => why.to_string(),
-    };
 
-    return Ok((soln1, soln2));
+    return Ok(solve(&diagnostic, num_bits));
+}
+
+// Reduces the diagnostic report under each of the criteria in turn, one rating per line.
+pub fn ratings(data: &str, pad: bool, criteria: &[BitCriteria]) -> Result<String, String> {
+    let (diagnostic, num_bits) = parse_with(data, pad).map_err(|e| e.to_string())?;
+
+    let lines = criteria
+        .iter()
+        .map(|c| match reduce_diagnostics(&diagnostic, num_bits, c) {
+            Ok(rating) => format!("{}: {}", c, rating),
+            Err(why) => format!("{}: {}", c, why),
+        })
+        .collect::<Vec<String>>();
+
+    return Ok(lines.join("\n"));
+}
+
+fn histogram(diagnostic: &[BitVector], num_bits: u32) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{:>5} {:>6} {:>8} {:>8} {:>5} {:>7}",
+        "bit", "column", "zeros", "ones", "gamma", "epsilon"
+    )];
+
+    for n in (0..num_bits).rev() {
+        let (zeros, ones) = count_bits(diagnostic, n);
+        let mut line = format!(
+            "{:>5} {:>6} {:>8} {:>8} {:>5} {:>7}",
+            n,
+            num_bits - n,
+            zeros,
+            ones,
+            BitCriteria::MOST_COMMON.select(zeros, ones),
+            BitCriteria::LEAST_COMMON.select(zeros, ones)
+        );
+
+        // a sensor bit that never changes is likely stuck
+        if ones == 0 {
+            line.push_str("  stuck at 0");
+        } else if zeros == 0 {
+            line.push_str("  stuck at 1");
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn reduction(diagnostic: &[BitVector], num_bits: u32, criteria: &BitCriteria) -> Vec<String> {
+    let (rounds, rating) = reduction_rounds(diagnostic, num_bits, criteria);
+
+    let mut lines = vec![format!(
+        "{:>5} {:>6} {:>10} {:>8} {:>8} {:>4} {:>9}",
+        "bit", "column", "candidates", "zeros", "ones", "kept", "remaining"
+    )];
+
+    for round in rounds.iter() {
+        lines.push(format!(
+            "{:>5} {:>6} {:>10} {:>8} {:>8} {:>4} {:>9}",
+            round.bit,
+            num_bits - round.bit,
+            round.zeros + round.ones,
+            round.zeros,
+            round.ones,
+            round.kept,
+            round.remaining
+        ));
+    }
+
+    lines.push(match rating {
+        Ok(rating) => format!("Rating: {}", rating),
+        Err(why) => format!("Rating: {}", why),
+    });
+
+    lines
+}
+
+// Counts of each bit position across the report, followed by how the candidates shrink
+// while finding each of the life support ratings.
+pub fn report(data: &str, pad: bool) -> Result<String, String> {
+    let (diagnostic, num_bits) = parse_with(data, pad).map_err(|e| e.to_string())?;
+
+    let sections = [
+        ("Bit counts".to_string(), histogram(&diagnostic, num_bits)),
+        (
+            format!("Oxygen generator rating ({})", BitCriteria::MOST_COMMON),
+            reduction(&diagnostic, num_bits, &BitCriteria::MOST_COMMON),
+        ),
+        (
+            format!("CO2 scrubber rating ({})", BitCriteria::LEAST_COMMON),
+            reduction(&diagnostic, num_bits, &BitCriteria::LEAST_COMMON),
+        ),
+    ];
+
+    let sections = sections
+        .iter()
+        .map(|(title, lines)| format!("{}\n{}", title, lines.join("\n")))
+        .collect::<Vec<String>>();
+
+    return Ok(sections.join("\n\n"));
 }
 
 #[cfg(test)]
@@ -157,9 +755,30 @@ mod tests {
     const EXAMPLE: &str = include_str!("../examples/day03.txt");
     const EXAMPLE_BITS: u32 = 5;
 
+    fn bits(line: &str) -> BitVector {
+        parse_binary(line).unwrap()
+    }
+
+    // repeats each line of the example until it is `width` bits wide
+    fn widen(width: usize) -> String {
+        EXAMPLE
+            .lines()
+            .map(|line| line.repeat(width / line.len() + 1)[..width].to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
     #[test]
     fn test_parse_binary() {
-        assert_eq!(parse_binary("00100"), Ok(4));
+        assert_eq!(
+            parse_binary("00100").map(|b| b.to_string()),
+            Ok("4".to_string())
+        );
+        assert_eq!(parse_binary("00100").map(|b| b.width), Ok(5));
+        assert_eq!(
+            parse_binary("").map(|b| b.width),
+            Err(Day03Error::ParseFailure("".to_string()))
+        );
         assert_eq!(
             parse_binary("notbinary"),
             Err(Day03Error::ParseFailure("notbinary".to_string()))
@@ -168,16 +787,103 @@ mod tests {
 
     #[test]
     fn test_parse() {
+        let (numbers, num_bits) = parse(EXAMPLE).unwrap();
         assert_eq!(
-            parse(EXAMPLE),
-            Ok((vec![4, 30, 22, 23, 21, 15, 7, 28, 16, 25, 2, 10], 5))
+            numbers
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>(),
+            vec!["4", "30", "22", "23", "21", "15", "7", "28", "16", "25", "2", "10"]
         );
+        assert_eq!(num_bits, 5);
         assert_eq!(
-            parse("notbinary\n00100"),
-            Err(Day03Error::ParseFailure("notbinary".to_string()))
+            parse("nobin\n00100"),
+            Err(Day03Error::ParseFailure("nobin".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_with() {
+        assert_eq!(
+            parse("101\n11\n010\n1100\n1"),
+            Err(Day03Error::InconsistentWidth(
+                3,
+                vec![(2, 2), (4, 4), (5, 1)]
+            ))
+        );
+
+        // the expected width is the most common one, even when the first line is the odd one
+        assert_eq!(
+            parse("1\n101\n010\n111"),
+            Err(Day03Error::InconsistentWidth(3, vec![(1, 1)]))
+        );
+        assert_eq!(
+            parse("10\n101\n01\n111"),
+            Err(Day03Error::InconsistentWidth(2, vec![(2, 3), (4, 3)]))
+        );
+
+        // blank lines are reported by line number, or padded to zeros
+        assert_eq!(
+            parse("101\n\n010"),
+            Err(Day03Error::InconsistentWidth(3, vec![(2, 0)]))
+        );
+        assert_eq!(
+            parse_with("101\n\n010", true),
+            Ok((vec![bits("101"), bits("000"), bits("010")], 3))
+        );
+        assert_eq!(parse("\n\n"), Err(Day03Error::EmptyData));
+
+        let (numbers, num_bits) = parse_with("101\n11\n1100", true).unwrap();
+        assert_eq!(num_bits, 4);
+        assert_eq!(numbers, vec![bits("0101"), bits("0011"), bits("1100")]);
+
+        assert_eq!(parse_with("", true), Err(Day03Error::EmptyData));
+        assert_eq!(
+            parse_with("101\n2", true),
+            Err(Day03Error::ParseFailure("2".to_string()))
         );
     }
 
+    #[test]
+    fn test_inconsistent_width_display() {
+        assert_eq!(
+            Day03Error::InconsistentWidth(5, vec![(2, 4), (7, 6)]).to_string(),
+            "Expected every line to be 5 bits wide, but line 2 is 4 bits wide, line 7 is 6 bits wide"
+        );
+    }
+
+    #[test]
+    fn test_bit_vector() {
+        let mut wide = BitVector::zeros(130);
+        assert_eq!(wide.to_string(), "0");
+
+        wide.set_bit(129, 1);
+        wide.set_bit(0, 1);
+        assert_eq!(wide.bit(129), 1);
+        assert_eq!(wide.bit(64), 0);
+        assert_eq!(wide.bit(200), 0);
+        assert_eq!(wide.to_string(), "680564733841876926926749214863536422913");
+
+        wide.set_bit(129, 0);
+        assert_eq!(wide.to_string(), "1");
+
+        let max = bits(&"1".repeat(64));
+        assert_eq!(max.to_string(), u64::MAX.to_string());
+        assert_eq!(
+            max.multiply(&max).to_string(),
+            (u64::MAX as u128 * u64::MAX as u128).to_string()
+        );
+        assert_eq!(max.multiply(&bits("0")).to_string(), "0");
+        assert_eq!(max.multiply(&max).width, 128);
+
+        let mut padded = bits("101");
+        padded.pad_to(70);
+        assert_eq!(padded.width, 70);
+        assert_eq!(padded.to_string(), "5");
+        padded.set_bit(69, 1);
+        assert_eq!(padded.bit(69), 1);
+    }
+
     #[test]
     fn test_common_bit() {
         assert_eq!(common_bit(&parse(EXAMPLE).unwrap().0, 0), 0);
@@ -189,12 +895,18 @@ mod tests {
 
     #[test]
     fn test_gamma_rate() {
-        assert_eq!(gamma_rate(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS), 22);
+        assert_eq!(
+            gamma_rate(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS),
+            bits("10110")
+        );
     }
 
     #[test]
     fn test_epsilon_rate() {
-        assert_eq!(epsilon_rate(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS), 9);
+        assert_eq!(
+            epsilon_rate(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS),
+            bits("01001")
+        );
     }
 
     #[test]
@@ -203,45 +915,261 @@ mod tests {
     }
 
     #[test]
-    fn test_oxygen_generator_rate_criteria() {
-        assert_eq!(oxygen_generator_rate_criteria(0b001, 0, 1), true);
-        assert_eq!(oxygen_generator_rate_criteria(0b010, 0, 0), true);
-        assert_eq!(oxygen_generator_rate_criteria(0b010, 1, 1), true);
-        assert_eq!(oxygen_generator_rate_criteria(0b100, 1, 0), true);
+    fn test_bit_criteria_select() {
+        let most = BitCriteria::MOST_COMMON;
+        assert_eq!(most.select(3, 1), 0);
+        assert_eq!(most.select(1, 3), 1);
+        assert_eq!(most.select(2, 2), 1);
 
-        assert_eq!(oxygen_generator_rate_criteria(0b001, 1, 1), false);
-        assert_eq!(oxygen_generator_rate_criteria(0b010, 1, 0), false);
-        assert_eq!(oxygen_generator_rate_criteria(0b010, 2, 1), false);
-        assert_eq!(oxygen_generator_rate_criteria(0b100, 2, 0), false);
+        let least = BitCriteria::LEAST_COMMON;
+        assert_eq!(least.select(3, 1), 1);
+        assert_eq!(least.select(1, 3), 0);
+        assert_eq!(least.select(2, 2), 0);
+
+        let most_tie_zero = BitCriteria {
+            ties_keep_one: false,
+            ..most
+        };
+        assert_eq!(most_tie_zero.select(2, 2), 0);
+        assert_eq!(most_tie_zero.select(1, 3), 1);
     }
 
     #[test]
-    fn test_oxygen_generator_rate() {
+    fn test_bit_criteria_from_str() {
+        assert_eq!("most".parse(), Ok(BitCriteria::MOST_COMMON));
+        assert_eq!("least".parse(), Ok(BitCriteria::LEAST_COMMON));
         assert_eq!(
-            oxygen_generator_rate(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS),
-            Some(23)
+            "least:1".parse(),
+            Ok(BitCriteria {
+                keep: Commonness::Least,
+                ties_keep_one: true
+            })
+        );
+        assert!("most:2".parse::<BitCriteria>().is_err());
+        assert!("median".parse::<BitCriteria>().is_err());
+        assert_eq!(
+            BitCriteria::LEAST_COMMON.to_string(),
+            "least common, ties keep 0"
         );
     }
 
     #[test]
-    fn test_co2_scrubber_rate_criteria() {
-        assert_eq!(co2_scrubber_rate_criteria(0b001, 0, 1), false);
-        assert_eq!(co2_scrubber_rate_criteria(0b010, 0, 0), false);
-        assert_eq!(co2_scrubber_rate_criteria(0b010, 1, 1), false);
-        assert_eq!(co2_scrubber_rate_criteria(0b100, 1, 0), false);
+    fn test_reduce_diagnostics() {
+        let (diagnostic, num_bits) = parse(EXAMPLE).unwrap();
+        let rating =
+            |criteria: &str| reduce_diagnostics(&diagnostic, num_bits, &criteria.parse().unwrap());
+
+        assert_eq!(rating("most"), Ok(bits("10111")));
+        assert_eq!(rating("least"), Ok(bits("01010")));
+        assert_eq!(rating("most:0"), Ok(bits("10110")));
+        assert_eq!(rating("least:1"), Ok(bits("01111")));
 
-        assert_eq!(co2_scrubber_rate_criteria(0b001, 1, 1), true);
-        assert_eq!(co2_scrubber_rate_criteria(0b010, 1, 0), true);
-        assert_eq!(co2_scrubber_rate_criteria(0b010, 2, 1), true);
-        assert_eq!(co2_scrubber_rate_criteria(0b100, 2, 0), true);
+        let (diagnostic, num_bits) = parse("10\n00").unwrap();
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::MOST_COMMON),
+            Ok(bits("10"))
+        );
+
+        // every remaining number shares its first bit, so the least common bit matches none
+        let (diagnostic, num_bits) = parse("10\n10").unwrap();
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::LEAST_COMMON),
+            Err(RatingFailure::Emptied { bit: 1, column: 1 })
+        );
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::MOST_COMMON),
+            Err(RatingFailure::Ambiguous(2))
+        );
+
+        let (diagnostic, num_bits) = parse("110\n011\n010").unwrap();
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::LEAST_COMMON),
+            Ok(bits("110"))
+        );
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &"least:1".parse().unwrap()),
+            Ok(bits("110"))
+        );
+        let (diagnostic, num_bits) = parse("110\n111\n011\n010").unwrap();
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &"least:1".parse().unwrap()),
+            Err(RatingFailure::Emptied { bit: 1, column: 2 })
+        );
+    }
+
+    #[test]
+    fn test_ratings() {
+        let criteria = ["most", "least:1"].map(|c| c.parse::<BitCriteria>().unwrap());
+        assert_eq!(
+            ratings(EXAMPLE, false, &criteria),
+            Ok("most common, ties keep 1: 23\nleast common, ties keep 1: 15".to_string())
+        );
+        assert_eq!(
+            ratings("11\n11", false, &[BitCriteria::LEAST_COMMON]),
+            Ok("least common, ties keep 0: no candidates were left after filtering on bit 1 (column 1)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reduction_rounds() {
+        let (diagnostic, num_bits) = parse(EXAMPLE).unwrap();
+        let (rounds, rating) = reduction_rounds(&diagnostic, num_bits, &BitCriteria::LEAST_COMMON);
+        assert_eq!(rating, Ok(bits("01010")));
+        assert_eq!(
+            rounds
+                .iter()
+                .map(|r| (r.bit, r.zeros, r.ones, r.kept, r.remaining))
+                .collect::<Vec<_>>(),
+            vec![(4, 5, 7, 0, 5), (3, 3, 2, 1, 2), (2, 1, 1, 0, 1)]
+        );
+
+        let (diagnostic, num_bits) = parse("11\n11").unwrap();
+        let (rounds, rating) = reduction_rounds(&diagnostic, num_bits, &BitCriteria::MOST_COMMON);
+        assert_eq!(rating, Err(RatingFailure::Ambiguous(2)));
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[1].remaining, 2);
+    }
+
+    #[test]
+    fn test_report() {
+        let output = report("100\n101", false).unwrap();
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[0], "Bit counts");
+        assert!(lines[2].ends_with("0  stuck at 1"));
+        assert!(lines[3].ends_with("1  stuck at 0"));
+        assert!(!lines[4].contains("stuck"));
+        assert!(output.contains("Rating: 5"));
+        assert!(
+            output.ends_with("Rating: no candidates were left after filtering on bit 2 (column 1)")
+        );
+
+        assert_eq!(
+            report("1\n10", false),
+            Err(Day03Error::InconsistentWidth(1, vec![(2, 2)]).to_string())
+        );
+    }
+
+    #[test]
+    fn test_oxygen_generator_rate() {
+        let (diagnostic, num_bits) = parse(EXAMPLE).unwrap();
+        let trie = DiagnosticTrie::new(&diagnostic, num_bits);
+        assert_eq!(oxygen_generator_rate(&trie), Ok(&bits("10111")));
     }
 
     #[test]
     fn test_co2_scrubber_rate() {
+        let (diagnostic, num_bits) = parse(EXAMPLE).unwrap();
+        let trie = DiagnosticTrie::new(&diagnostic, num_bits);
+        assert_eq!(co2_scrubber_rate(&trie), Ok(&bits("01010")));
+    }
+
+    #[test]
+    fn test_diagnostic_trie() {
+        let (diagnostic, num_bits) = parse("101\n100\n101\n011").unwrap();
+        let trie = DiagnosticTrie::new(&diagnostic, num_bits);
+
+        // the lone line starting with 0 stays a leaf just below the root, while the
+        // duplicated 101 lines are expanded to full depth
+        assert_eq!(trie.nodes.len(), 6);
+        assert_eq!(trie.nodes[0].count, 4);
+        assert_eq!(trie.split(0, 2).map(|(_, count)| count), [1, 3]);
+
         assert_eq!(
-            co2_scrubber_rate(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS),
-            Some(10)
+            trie.rating(&BitCriteria::MOST_COMMON),
+            Err(RatingFailure::Ambiguous(2))
         );
+        assert_eq!(trie.rating(&BitCriteria::LEAST_COMMON), Ok(&bits("011")));
+        assert_eq!(trie.rating(&"least:1".parse().unwrap()), Ok(&bits("011")));
+    }
+
+    #[test]
+    fn test_diagnostic_trie_matches_reduce_diagnostics() {
+        let reports = [
+            EXAMPLE.to_string(),
+            widen(64),
+            widen(130),
+            "10\n10".to_string(),
+            "10\n00".to_string(),
+            "1".to_string(),
+            "0\n0".to_string(),
+        ];
+        let criteria = ["most", "least", "most:0", "least:1"].map(|c| c.parse().unwrap());
+
+        for report in reports.iter() {
+            let (diagnostic, num_bits) = parse(report).unwrap();
+            let trie = DiagnosticTrie::new(&diagnostic, num_bits);
+            for c in criteria.iter() {
+                assert_eq!(
+                    trie.rating(c).cloned(),
+                    reduce_diagnostics(&diagnostic, num_bits, c),
+                    "{} on {}",
+                    c,
+                    report
+                );
+            }
+        }
+    }
+
+    // a deterministic report of `lines` pseudo random `width` bit lines
+    fn random_report(lines: usize, width: usize) -> String {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut report = String::with_capacity(lines * (width + 1));
+        for _ in 0..lines {
+            for _ in 0..width {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                report.push(if state & 1 == 0 { '0' } else { '1' });
+            }
+            report.push('\n');
+        }
+
+        report
+    }
+
+    // Compares the trie against filtering the report once per bit, and shows which of them
+    // solution 2 uses. Run with
+    // `cargo test --release bench_ratings -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_ratings_million_lines() {
+        for width in [12, 16, 64] {
+            let (diagnostic, num_bits) = parse(&random_report(1_000_000, width)).unwrap();
+
+            let start = std::time::Instant::now();
+            let oxy = reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::MOST_COMMON);
+            let co2 = reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::LEAST_COMMON);
+            let filtering = start.elapsed();
+
+            let start = std::time::Instant::now();
+            let trie = DiagnosticTrie::new(&diagnostic, num_bits);
+            let ratings = (oxygen_generator_rate(&trie), co2_scrubber_rate(&trie));
+            let walking = start.elapsed();
+
+            assert_eq!(ratings.0.cloned(), oxy);
+            assert_eq!(ratings.1.cloned(), co2);
+            println!(
+                "{} bits: filtering {:?}, trie {:?} with {} nodes, solution 2 uses the {}",
+                width,
+                filtering,
+                walking,
+                trie.nodes.len(),
+                if use_trie(diagnostic.len(), num_bits) {
+                    "trie"
+                } else {
+                    "filter"
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_use_trie() {
+        assert!(use_trie(1_000_000, 12));
+        assert!(!use_trie(1_000_000, 16));
+        assert!(!use_trie(1_000_000, 64));
+        assert!(!use_trie(1_000_000, 200));
+        assert!(!use_trie(12, 5));
     }
 
     #[test]
@@ -249,14 +1177,92 @@ mod tests {
         assert_eq!(
             solution2(&parse(EXAMPLE).unwrap().0, EXAMPLE_BITS),
             Ok("230".to_string())
+        );
+
+        // enough lines for the trie, which must fail the same way as filtering
+        let (diagnostic, num_bits) = parse(&vec![EXAMPLE; 200].join("\n")).unwrap();
+        assert!(use_trie(diagnostic.len(), num_bits));
+        assert_eq!(
+            solution2(&diagnostic, num_bits),
+            Err(Day03Error::NoOxygenRate(RatingFailure::Ambiguous(200)))
+        );
+        assert_eq!(
+            reduce_diagnostics(&diagnostic, num_bits, &BitCriteria::MOST_COMMON),
+            Err(RatingFailure::Ambiguous(200))
         )
     }
 
+    #[test]
+    fn test_wide_reports() {
+        let (diagnostic, num_bits) = parse(&widen(64)).unwrap();
+        assert_eq!(num_bits, 64);
+        assert_eq!(
+            gamma_rate(&diagnostic, num_bits).to_string(),
+            "13091237729729359211"
+        );
+        assert_eq!(
+            epsilon_rate(&diagnostic, num_bits).to_string(),
+            "5355506343980192404"
+        );
+        let trie = DiagnosticTrie::new(&diagnostic, num_bits);
+        assert_eq!(
+            oxygen_generator_rate(&trie).map(|r| r.to_string()),
+            Ok("13686293990171602811".to_string())
+        );
+        assert_eq!(
+            co2_scrubber_rate(&trie).map(|r| r.to_string()),
+            Ok("5950562604422436005".to_string())
+        );
+
+        assert_eq!(
+            solutions(&widen(64)),
+            Ok((
+                Ok("70110206712118434709474898247409633244".to_string()),
+                Ok("81441149211046666586341480999925610055".to_string())
+            ))
+        );
+        assert_eq!(
+            solutions(&widen(130)),
+            Ok((
+                Ok(
+                    "381716273365054846099763663379316641080770347712327305255853944598227756350022"
+                        .to_string()
+                ),
+                Ok(
+                    "443407792292740477792654760491125391154430201888056970751749531604001939194470"
+                        .to_string()
+                )
+            ))
+        );
+    }
+
     #[test]
     fn test_solutions() {
         assert_eq!(
             solutions(EXAMPLE),
-            Ok(("198".to_string(), "230".to_string()))
+            Ok((Ok("198".to_string()), Ok("230".to_string())))
+        );
+
+        // dropping the leading zeros from the example leaves the answers unchanged once the
+        // lines are padded back out
+        let trimmed = EXAMPLE
+            .lines()
+            .map(|line| line.trim_start_matches('0'))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        assert_eq!(
+            solutions_with(&trimmed, true),
+            Ok((Ok("198".to_string()), Ok("230".to_string())))
+        );
+        assert!(solutions(&trimmed).is_err());
+
+        // part 1 is still answered when part 2 cannot find a rating
+        assert_eq!(
+            solutions("10\n10"),
+            Ok((
+                Ok("2".to_string()),
+                Err("Unable to find an Oxygen rate: 2 identical candidates were left after filtering on every bit".to_string())
+            ))
         );
     }
 }
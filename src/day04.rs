@@ -21,11 +21,12 @@ pub enum Day04Error {
     ParseBoardFailure(String, String),
     ParseLotsFailure(String),
     MissingInputData(String),
+    RaggedBoard(String, usize, usize, usize),
 }
 
 impl std::fmt::Display for Day04Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match &*self {
+        match self {
             Day04Error::ParseBoardFailure(board, piece) => {
                 write!(
                     f,
@@ -43,50 +44,163 @@ impl std::fmt::Display for Day04Error {
             Day04Error::MissingInputData(why) => {
                 write!(f, "Missing input data: {}", why)
             }
+            Day04Error::RaggedBoard(board, row, expected, actual) => {
+                write!(
+                    f,
+                    "Unable to parse board '{}'. Row {} has {} numbers, expected {}",
+                    board, row, actual, expected
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinRule {
+    Rows,
+    Columns,
+    Diagonals,
+    Corners,
+    Blackout,
+}
+
+impl WinRule {
+    // the rules of the puzzle, where any full row or column wins
+    pub const STANDARD: [WinRule; 2] = [WinRule::Rows, WinRule::Columns];
+
+    // The groups of cell indices this rule accepts on a board, any one of which wins once
+    // every number in it is marked. Diagonals only exist on square boards.
+    pub fn lines(&self, board: &BingoBoard) -> Vec<Vec<usize>> {
+        let (width, height) = (board.width, board.height);
+
+        match self {
+            WinRule::Rows => (0..height)
+                .map(|i| (0..width).map(|j| i * width + j).collect())
+                .collect(),
+            WinRule::Columns => (0..width)
+                .map(|j| (0..height).map(|i| i * width + j).collect())
+                .collect(),
+            WinRule::Diagonals if width == height => vec![
+                (0..width).map(|i| i * width + i).collect(),
+                (0..width).map(|i| i * width + (width - 1 - i)).collect(),
+            ],
+            WinRule::Diagonals => vec![],
+            WinRule::Corners => vec![vec![0, width - 1, (height - 1) * width, height * width - 1]],
+            WinRule::Blackout => vec![(0..width * height).collect()],
+        }
+    }
+}
+
+impl std::str::FromStr for WinRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rows" => Ok(WinRule::Rows),
+            "columns" => Ok(WinRule::Columns),
+            "diagonals" => Ok(WinRule::Diagonals),
+            "corners" => Ok(WinRule::Corners),
+            "blackout" => Ok(WinRule::Blackout),
+            _ => Err(format!(
+                "Unknown win rule {}, expected one of rows, columns, diagonals, corners or blackout",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for WinRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            WinRule::Rows => "rows",
+            WinRule::Columns => "columns",
+            WinRule::Diagonals => "diagonals",
+            WinRule::Corners => "corners",
+            WinRule::Blackout => "blackout",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BingoBoard {
+    width: usize,
+    height: usize,
+    // numbers in row major order
+    cells: Vec<u32>,
+}
+
+impl BingoBoard {
+    // the first fully marked line of any of the rules, as the rule and the line's numbers
+    pub fn winning_line(
+        &self,
+        numbers: &std::collections::HashSet<u32>,
+        rules: &[WinRule],
+    ) -> Option<(WinRule, Vec<u32>)> {
+        for rule in rules.iter() {
+            let marked = rule
+                .lines(self)
+                .into_iter()
+                .find(|line| line.iter().all(|&i| numbers.contains(&self.cells[i])));
+
+            if let Some(line) = marked {
+                return Some((*rule, line.into_iter().map(|i| self.cells[i]).collect()));
+            }
         }
+
+        return None;
+    }
+
+    pub fn unmarked_sum(&self, numbers: &std::collections::HashSet<u32>) -> u32 {
+        self.cells.iter().filter(|n| !numbers.contains(n)).sum()
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Day04 {
     numbers: Vec<u32>,
-    boards: Vec<Vec<u32>>,
+    boards: Vec<BingoBoard>,
 }
 
-pub fn parse_bingo_board(data: &str) -> Result<Vec<u32>, Day04Error> {
-    data.trim()
-        .split(&[' ', '\n'][..])
-        .filter(|&s| s != "")
-        .map(|elem| {
-            elem.trim()
-                .parse()
-                .ok()
-                .ok_or(Day04Error::ParseBoardFailure(
-                    data.to_string(),
-                    elem.to_string(),
-                ))
+// The board's width and height are taken from its rows, which must all be the same length.
+pub fn parse_bingo_board(data: &str) -> Result<BingoBoard, Day04Error> {
+    let rows = data
+        .trim()
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|elem| {
+                    elem.parse().ok().ok_or(Day04Error::ParseBoardFailure(
+                        data.to_string(),
+                        elem.to_string(),
+                    ))
+                })
+                .collect::<Result<Vec<u32>, Day04Error>>()
         })
-        .collect::<Result<Vec<u32>, Day04Error>>()
-}
+        .collect::<Result<Vec<Vec<u32>>, Day04Error>>()?;
 
-pub fn is_bingo(board: &Vec<u32>, numbers: &std::collections::HashSet<u32>) -> bool {
-    // check rows and columns
-    for i in 0..5 {
-        let mut row = std::collections::HashSet::new();
-        let mut col = std::collections::HashSet::new();
-        for j in 0..5 {
-            row.insert(board[i * 5 + j]);
-            col.insert(board[5 * j + i]);
-        }
+    let width = rows.first().map(|r| r.len()).unwrap_or(0);
+    if width == 0 {
+        return Err(Day04Error::MissingInputData(
+            "Found an empty bingo board".to_string(),
+        ));
+    }
 
-        if row.is_subset(numbers) {
-            return true;
-        } else if col.is_subset(numbers) {
-            return true;
-        }
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+        return Err(Day04Error::RaggedBoard(
+            data.to_string(),
+            i + 1,
+            width,
+            row.len(),
+        ));
     }
 
-    return false;
+    return Ok(BingoBoard {
+        width,
+        height: rows.len(),
+        cells: rows.into_iter().flatten().collect(),
+    });
 }
 
 pub fn parse(data: &str) -> Result<Day04, Day04Error> {
@@ -105,80 +219,154 @@ pub fn parse(data: &str) -> Result<Day04, Day04Error> {
         .collect::<Result<Vec<u32>, _>>()?;
 
     let boards = boards
+        .trim_end()
         .split("\n\n")
-        .map(|board| parse_bingo_board(board))
-        .collect::<Result<Vec<Vec<u32>>, Day04Error>>()?;
+        .map(parse_bingo_board)
+        .collect::<Result<Vec<BingoBoard>, Day04Error>>()?;
 
     return Ok(Day04 { numbers, boards });
 }
 
-pub fn solution1(data: &Day04) -> String {
-    let mut played_numbers = std::collections::HashSet::new();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardWin {
+    pub board: usize,
+    // the 1-based turn of the draw that completed the board
+    pub turn: usize,
+    pub number: u32,
+    pub rule: WinRule,
+    pub line: Vec<u32>,
+    pub score: u32,
+}
 
-    for num in data.numbers.iter() {
-        played_numbers.insert(*num);
+impl std::fmt::Display for BoardWin {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let line = self
+            .line
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>();
+
+        write!(
+            f,
+            "Board {} won on turn {} drawing {}, completing {} {} for a score of {}",
+            self.board + 1,
+            self.turn,
+            self.number,
+            self.rule,
+            line.join(" "),
+            self.score
+        )
+    }
+}
+
+// Every board's win in the order they happened, with boards winning on the same turn in
+// board order, and the boards still without a win once every number has been drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    pub wins: Vec<BoardWin>,
+    pub never_won: Vec<usize>,
+}
 
-        if let Some(winner) = data
-            .boards
+impl std::fmt::Display for Timeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut lines = self
+            .wins
             .iter()
-            .filter(|&b| is_bingo(b, &played_numbers))
-            .next()
-        {
-            let unmarked: u32 = winner
-                .clone()
-                .into_iter()
-                .collect::<std::collections::HashSet<u32>>()
-                .difference(&played_numbers)
-                .sum();
+            .map(|w| w.to_string())
+            .collect::<Vec<String>>();
 
-            return (unmarked * num).to_string();
+        for board in self.never_won.iter() {
+            lines.push(format!("Board {} never won", board + 1));
         }
-    }
 
-    return "No solution found".to_string();
+        write!(f, "{}", lines.join("\n"))
+    }
 }
 
-pub fn solution2(data: &Day04) -> String {
+pub fn play(data: &Day04, rules: &[WinRule]) -> Timeline {
     let mut played_numbers = std::collections::HashSet::new();
+    let mut playing = (0..data.boards.len()).collect::<Vec<usize>>();
+    let mut wins = Vec::new();
 
-    let mut winners = std::collections::HashSet::new();
-
-    for num in data.numbers.iter() {
+    for (turn, num) in data.numbers.iter().enumerate() {
+        if playing.is_empty() {
+            break;
+        }
         played_numbers.insert(*num);
 
-        for winner in data.boards.iter().filter(|&b| is_bingo(b, &played_numbers)) {
-            if !winners.contains(&winner) && winners.len() + 1 == data.boards.len() {
-                let unmarked: u32 = winner
-                    .clone()
-                    .into_iter()
-                    .collect::<std::collections::HashSet<u32>>()
-                    .difference(&played_numbers)
-                    .sum();
-
-                return (unmarked * num).to_string();
-            } else if !winners.contains(&winner) {
-                winners.insert(winner);
+        playing.retain(|&board| {
+            let winner = &data.boards[board];
+            match winner.winning_line(&played_numbers, rules) {
+                Some((rule, line)) => {
+                    wins.push(BoardWin {
+                        board,
+                        turn: turn + 1,
+                        number: *num,
+                        rule,
+                        line,
+                        score: winner.unmarked_sum(&played_numbers) * num,
+                    });
+                    false
+                }
+                None => true,
             }
-        }
+        });
     }
 
-    return "No solution found".to_string();
+    return Timeline {
+        wins,
+        never_won: playing,
+    };
 }
 
-pub fn solutions(data: &str) -> Result<(String, String), String> {
+pub fn solution1(timeline: &Timeline) -> String {
+    match timeline.wins.first() {
+        Some(win) => win.score.to_string(),
+        None => "No solution found".to_string(),
+    }
+}
+
+// the last board can only be chosen once every board has won
+pub fn solution2(timeline: &Timeline) -> String {
+    match timeline.wins.last() {
+        Some(win) if timeline.never_won.is_empty() => win.score.to_string(),
+        _ => "No solution found".to_string(),
+    }
+}
+
+pub fn solutions_with(data: &str, rules: &[WinRule]) -> Result<(String, String), String> {
     let day = parse(data).map_err(|e| e.to_string())?;
-    let soln1 = solution1(&day);
-    let soln2 = solution2(&day);
+    let timeline = play(&day, rules);
+    let soln1 = solution1(&timeline);
+    let soln2 = solution2(&timeline);
 
     return Ok((soln1, soln2));
 }
 
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    solutions_with(data, &WinRule::STANDARD)
+}
+
+pub fn report(data: &str, rules: &[WinRule]) -> Result<String, String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+
+    return Ok(play(&day, rules).to_string());
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
     const EXAMPLE: &str = include_str!("../examples/day04.txt");
 
+    fn square_board(cells: Vec<u32>) -> BingoBoard {
+        BingoBoard {
+            width: 5,
+            height: 5,
+            cells,
+        }
+    }
+
     #[test]
     fn test_parse_bingo_board() {
         let data = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
@@ -188,7 +376,31 @@ mod tests {
         ];
         let actual = parse_bingo_board(data);
 
-        assert_eq!(actual, Ok(expected));
+        assert_eq!(actual, Ok(square_board(expected)));
+
+        let data = "1 2 3\n4 5 6";
+        let expected = BingoBoard {
+            width: 3,
+            height: 2,
+            cells: vec![1, 2, 3, 4, 5, 6],
+        };
+        assert_eq!(parse_bingo_board(data), Ok(expected));
+
+        assert_eq!(
+            parse_bingo_board("1 2\n3 4 5\n6 7"),
+            Err(Day04Error::RaggedBoard(
+                "1 2\n3 4 5\n6 7".to_string(),
+                2,
+                2,
+                3
+            ))
+        );
+        assert_eq!(
+            parse_bingo_board(""),
+            Err(Day04Error::MissingInputData(
+                "Found an empty bingo board".to_string()
+            ))
+        );
     }
 
     #[test]
@@ -211,7 +423,10 @@ mod tests {
                     14, 21, 17, 24, 4, 10, 16, 15, 9, 19, 18, 8, 23, 26, 20, 22, 11, 13, 6, 5, 2,
                     0, 12, 3, 7,
                 ],
-            ],
+            ]
+            .into_iter()
+            .map(square_board)
+            .collect(),
         });
         assert_eq!(parse(EXAMPLE), expected);
     }
@@ -220,18 +435,133 @@ mod tests {
     fn test_solution1() {
         let data = parse(EXAMPLE).unwrap();
         let expected = "4512";
-        assert_eq!(solution1(&data), expected);
+        assert_eq!(solution1(&play(&data, &WinRule::STANDARD)), expected);
     }
 
     #[test]
     fn test_solution2() {
         let data = parse(EXAMPLE).unwrap();
         let expected = "1924";
-        assert_eq!(solution2(&data), expected);
+        assert_eq!(solution2(&play(&data, &WinRule::STANDARD)), expected);
+    }
+
+    #[test]
+    fn test_winning_line_any_size() {
+        let numbers = vec![2, 5, 8].into_iter().collect();
+        assert!(parse_bingo_board("1 2 3\n4 5 6\n7 8 9")
+            .unwrap()
+            .winning_line(&numbers, &WinRule::STANDARD)
+            .is_some());
+
+        let board = parse_bingo_board("1 2 3 4\n5 6 7 8").unwrap();
+        assert!(board
+            .winning_line(&vec![1, 2, 3].into_iter().collect(), &WinRule::STANDARD)
+            .is_none());
+        assert!(board
+            .winning_line(&vec![5, 6, 7, 8].into_iter().collect(), &WinRule::STANDARD)
+            .is_some());
+        assert!(board
+            .winning_line(&vec![4, 8].into_iter().collect(), &WinRule::STANDARD)
+            .is_some());
+        assert_eq!(board.unmarked_sum(&vec![4, 8].into_iter().collect()), 24);
+    }
+
+    #[test]
+    fn test_win_rules() {
+        let board = parse_bingo_board(EXAMPLE.split("\n\n").nth(1).unwrap()).unwrap();
+        let marked = |numbers: Vec<u32>| numbers.into_iter().collect();
+
+        let diagonal = marked(vec![22, 2, 14, 18, 19]);
+        assert!(board.winning_line(&diagonal, &WinRule::STANDARD).is_none());
+        assert!(board
+            .winning_line(&diagonal, &[WinRule::Diagonals])
+            .is_some());
+        assert!(board
+            .winning_line(&marked(vec![0, 4, 14, 10, 1]), &[WinRule::Diagonals])
+            .is_some());
+
+        let corners = marked(vec![22, 0, 1, 19]);
+        assert!(board.winning_line(&corners, &[WinRule::Corners]).is_some());
+        assert!(board
+            .winning_line(&corners, &[WinRule::Rows, WinRule::Diagonals])
+            .is_none());
+
+        let row = marked(vec![22, 13, 17, 11, 0]);
+        assert!(board.winning_line(&row, &[WinRule::Rows]).is_some());
+        assert!(board
+            .winning_line(&row, &[WinRule::Columns, WinRule::Blackout])
+            .is_none());
+        assert!(board
+            .winning_line(&marked((0..=24).collect()), &[WinRule::Blackout])
+            .is_some());
+
+        // diagonals never win on a board that is not square
+        let board = parse_bingo_board("1 2 3\n4 5 6").unwrap();
+        assert!(board
+            .winning_line(&marked((1..=6).collect()), &[WinRule::Diagonals])
+            .is_none());
+        assert!(board
+            .winning_line(&marked(vec![1, 3, 4, 6]), &[WinRule::Corners])
+            .is_some());
+    }
+
+    #[test]
+    fn test_parse_win_rule() {
+        assert_eq!("corners".parse::<WinRule>(), Ok(WinRule::Corners));
+        assert_eq!("blackout".parse::<WinRule>(), Ok(WinRule::Blackout));
+        assert!("stars".parse::<WinRule>().is_err());
+    }
+
+    #[test]
+    fn test_solutions_with() {
+        assert_eq!(
+            solutions_with(EXAMPLE, &WinRule::STANDARD),
+            Ok(("4512".to_string(), "1924".to_string()))
+        );
+        assert_eq!(
+            solutions_with(EXAMPLE, &[WinRule::Blackout]),
+            solutions_with(EXAMPLE, &[WinRule::Blackout, WinRule::Blackout])
+        );
+    }
+
+    #[test]
+    fn test_play() {
+        let data = parse(EXAMPLE).unwrap();
+        let timeline = play(&data, &WinRule::STANDARD);
+
+        assert_eq!(
+            timeline
+                .wins
+                .iter()
+                .map(|w| (w.board, w.turn, w.number, w.score))
+                .collect::<Vec<_>>(),
+            vec![(2, 12, 24, 4512), (0, 14, 16, 2192), (1, 15, 13, 1924)]
+        );
+        assert_eq!(timeline.wins[0].rule, WinRule::Rows);
+        assert_eq!(timeline.wins[0].line, vec![14, 21, 17, 24, 4]);
+        assert!(timeline.never_won.is_empty());
+        assert_eq!(
+            timeline.wins[0].to_string(),
+            "Board 3 won on turn 12 drawing 24, completing rows 14 21 17 24 4 for a score of 4512"
+        );
+
+        // no board is complete before the first draws run out
+        let data = parse("7,4,9\n\n1 2\n3 4\n\n4 7\n9 8").unwrap();
+        let timeline = play(&data, &WinRule::STANDARD);
+        assert_eq!(timeline.wins.len(), 1);
+        assert_eq!(timeline.wins[0].board, 1);
+        assert_eq!(timeline.wins[0].turn, 2);
+        assert_eq!(timeline.never_won, vec![0]);
+        assert_eq!(solution1(&timeline), "68");
+        assert_eq!(solution2(&timeline), "No solution found");
+        assert_eq!(
+            timeline.to_string().lines().last(),
+            Some("Board 1 never won")
+        );
     }
 
     #[test]
-    fn test_is_bingo() {
+    fn test_winning_line() {
         let tests: Vec<(Vec<u32>, std::collections::HashSet<u32>, bool)> = vec![
             // valid row bingos
             (
@@ -344,7 +674,9 @@ mod tests {
 
         for (board, numbers, expected) in tests {
             assert_eq!(
-                is_bingo(&board, &numbers),
+                square_board(board.clone())
+                    .winning_line(&numbers, &WinRule::STANDARD)
+                    .is_some(),
                 expected,
                 "board={:?} and numbers={:?}",
                 board,
@@ -118,7 +118,7 @@ pub enum Day05Error {
 
 impl std::fmt::Display for Day05Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match &*self {
+        match self {
             Day05Error::ParseLineFailure(line) => {
                 write!(f, "Failed to parse line: {}", line)
             }
@@ -167,17 +167,17 @@ pub fn is_diagonal(line: &Line) -> bool {
 
 pub fn line_to_points(line: &Line) -> std::collections::HashSet<(i32, i32)> {
     let xs: Box<dyn Iterator<Item = i32>> = if line.start_x < line.end_x {
-        Box::new((line.start_x..=line.end_x).into_iter())
+        Box::new(line.start_x..=line.end_x)
     } else if line.start_x > line.end_x {
-        Box::new((line.end_x..=line.start_x).rev().into_iter())
+        Box::new((line.end_x..=line.start_x).rev())
     } else {
         Box::new(std::iter::repeat(line.start_x))
     };
 
     let ys: Box<dyn Iterator<Item = i32>> = if line.start_y < line.end_y {
-        Box::new((line.start_y..=line.end_y).into_iter())
+        Box::new(line.start_y..=line.end_y)
     } else if line.start_y > line.end_y {
-        Box::new((line.end_y..=line.start_y).rev().into_iter())
+        Box::new((line.end_y..=line.start_y).rev())
     } else {
         Box::new(std::iter::repeat(line.start_y))
     };
@@ -185,11 +185,11 @@ pub fn line_to_points(line: &Line) -> std::collections::HashSet<(i32, i32)> {
     xs.zip(ys).collect()
 }
 
-pub fn solution1(data: &Vec<Line>) -> String {
+pub fn solution1(data: &[Line]) -> String {
     let pt_sets = data
         .iter()
         .filter(|&l| is_horizontal(l) || is_vertical(l))
-        .map(|l| line_to_points(&l));
+        .map(line_to_points);
 
     let mut pts = std::collections::HashMap::new();
     for pt_set in pt_sets {
@@ -198,20 +198,16 @@ pub fn solution1(data: &Vec<Line>) -> String {
         }
     }
 
-    let soln = pts
-        .into_iter()
-        .filter(|&(_, total)| total > 1)
-        .map(|(_, total)| total)
-        .count();
+    let soln = pts.into_iter().filter(|&(_, total)| total > 1).count();
 
     return soln.to_string();
 }
 
-pub fn solution2(data: &Vec<Line>) -> String {
+pub fn solution2(data: &[Line]) -> String {
     let pt_sets = data
         .iter()
         .filter(|&l| is_horizontal(l) || is_vertical(l) || is_diagonal(l))
-        .map(|l| line_to_points(&l));
+        .map(line_to_points);
 
     let mut pts = std::collections::HashMap::new();
     for pt_set in pt_sets {
@@ -220,11 +216,7 @@ pub fn solution2(data: &Vec<Line>) -> String {
         }
     }
 
-    let soln = pts
-        .into_iter()
-        .filter(|&(_, total)| total > 1)
-        .map(|(_, total)| total)
-        .count();
+    let soln = pts.into_iter().filter(|&(_, total)| total > 1).count();
 
     return soln.to_string();
 }
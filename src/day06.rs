@@ -96,7 +96,7 @@ pub enum Day06Error {
 
 impl std::fmt::Display for Day06Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match &*self {
+        match self {
             Day06Error::LanternFishParseFailure(fish) => {
                 write!(f, "Unable to parse fish '{}'.", fish)
             }
@@ -115,18 +115,18 @@ pub fn parse(data: &str) -> Result<Vec<LanternFish>, Day06Error> {
         .collect::<Result<Vec<LanternFish>, Day06Error>>()
 }
 
-pub fn solution1(data: &Vec<LanternFish>) -> String {
-    let mut school: LanternFishSchool = data.clone().into_iter().collect();
-    for i in 1..=80 {
+pub fn solution1(data: &[LanternFish]) -> String {
+    let mut school: LanternFishSchool = data.iter().copied().collect();
+    for _ in 1..=80 {
         school = school.next_generation();
     }
 
     school.size().to_string()
 }
 
-pub fn solution2(data: &Vec<LanternFish>) -> String {
-    let mut school: LanternFishSchool = data.clone().into_iter().collect();
-    for i in 1..=256 {
+pub fn solution2(data: &[LanternFish]) -> String {
+    let mut school: LanternFishSchool = data.iter().copied().collect();
+    for _ in 1..=256 {
         school = school.next_generation();
     }
 
@@ -187,7 +187,7 @@ mod tests {
     #[test]
     fn test_lanterfishschool_from_iter() {
         let fish = vec![LanternFish(0), LanternFish(0), LanternFish(1)];
-        let school = LanternFishSchool::from_iter(fish.into_iter());
+        let school = LanternFishSchool::from_iter(fish);
         assert_eq!(school.size(), 3)
     }
 
@@ -211,10 +211,10 @@ mod tests {
     fn test_lanterfishschool_add() {
         let mut school = LanternFishSchool::new();
         school.add(LanternFish(0));
-        assert_eq!(school.spawn_timer.get(0), Some(&1));
+        assert_eq!(school.spawn_timer.first(), Some(&1));
 
         school.add(LanternFish(0));
-        assert_eq!(school.spawn_timer.get(0), Some(&2));
+        assert_eq!(school.spawn_timer.first(), Some(&2));
     }
 
     #[test]
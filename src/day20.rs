@@ -0,0 +1,314 @@
+/*
+Advent of Code 2021 solutions
+Copyright (C) 2021  Alex Bechanko
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+const ALGORITHM_SIZE: usize = 512;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Day20Error {
+    MissingInputData(String),
+    InvalidAlgorithmLength(usize),
+    InvalidPixel(char),
+    RaggedImage(usize, usize, usize),
+}
+
+impl std::fmt::Display for Day20Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day20Error::MissingInputData(why) => {
+                write!(f, "Missing input data: {}", why)
+            }
+            Day20Error::InvalidAlgorithmLength(len) => {
+                write!(
+                    f,
+                    "Image enhancement algorithm must be {} pixels long, found {}",
+                    ALGORITHM_SIZE, len
+                )
+            }
+            Day20Error::InvalidPixel(c) => {
+                write!(f, "Failed to parse '{}' as a pixel", c)
+            }
+            Day20Error::RaggedImage(row, expected, actual) => {
+                write!(
+                    f,
+                    "Image row {} has {} pixels, expected {}",
+                    row, actual, expected
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+    background: bool,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, pixels: Vec<bool>) -> Option<Image> {
+        if pixels.len() != width * height {
+            return None;
+        }
+
+        Some(Image {
+            width,
+            height,
+            pixels,
+            background: false,
+        })
+    }
+
+    pub fn pixel(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return self.background;
+        }
+
+        self.pixels[y as usize * self.width + x as usize]
+    }
+
+    pub fn enhance(&self, algorithm: &[bool; ALGORITHM_SIZE]) -> Image {
+        // the image grows by one pixel in every direction per enhancement
+        let width = self.width + 2;
+        let height = self.height + 2;
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                let mut index = 0;
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        index = index << 1 | self.pixel(x - 1 + dx, y - 1 + dy) as usize;
+                    }
+                }
+                pixels.push(algorithm[index]);
+            }
+        }
+
+        // every pixel in the infinite background sees nine background pixels, so it
+        // becomes the first or last entry of the algorithm
+        let background = if self.background {
+            algorithm[ALGORITHM_SIZE - 1]
+        } else {
+            algorithm[0]
+        };
+
+        Image {
+            width,
+            height,
+            pixels,
+            background,
+        }
+    }
+
+    pub fn lit_pixels(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+
+        Some(self.pixels.iter().filter(|&&p| p).count())
+    }
+}
+
+impl std::fmt::Display for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in self.pixels.chunks(self.width) {
+            let line: String = row.iter().map(|&p| if p { '#' } else { '.' }).collect();
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Day20 {
+    algorithm: [bool; ALGORITHM_SIZE],
+    image: Image,
+}
+
+pub fn parse_pixel(c: char) -> Result<bool, Day20Error> {
+    match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(Day20Error::InvalidPixel(c)),
+    }
+}
+
+pub fn parse_algorithm(line: &str) -> Result<[bool; ALGORITHM_SIZE], Day20Error> {
+    let pixels = line
+        .trim()
+        .chars()
+        .map(parse_pixel)
+        .collect::<Result<Vec<bool>, Day20Error>>()?;
+
+    let len = pixels.len();
+    pixels
+        .try_into()
+        .map_err(|_| Day20Error::InvalidAlgorithmLength(len))
+}
+
+pub fn parse_image(data: &str) -> Result<Image, Day20Error> {
+    let rows = data
+        .lines()
+        .map(|line| {
+            line.trim()
+                .chars()
+                .map(parse_pixel)
+                .collect::<Result<Vec<bool>, Day20Error>>()
+        })
+        .collect::<Result<Vec<Vec<bool>>, Day20Error>>()?;
+
+    let width = rows
+        .first()
+        .map(|row| row.len())
+        .ok_or(Day20Error::MissingInputData(
+            "Unable to find image".to_string(),
+        ))?;
+
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+        return Err(Day20Error::RaggedImage(i + 1, width, row.len()));
+    }
+
+    let height = rows.len();
+    let pixels = rows.into_iter().flatten().collect();
+
+    Image::new(width, height, pixels).ok_or(Day20Error::MissingInputData(
+        "Unable to find image".to_string(),
+    ))
+}
+
+pub fn parse(data: &str) -> Result<Day20, Day20Error> {
+    let (algorithm, image) = data.split_once("\n\n").ok_or(Day20Error::MissingInputData(
+        "Unable to find image enhancement algorithm".to_string(),
+    ))?;
+
+    let algorithm = parse_algorithm(algorithm)?;
+    let image = parse_image(image.trim())?;
+
+    return Ok(Day20 { algorithm, image });
+}
+
+pub fn enhance_times(data: &Day20, times: usize) -> Image {
+    (0..times).fold(data.image.clone(), |image, _| {
+        image.enhance(&data.algorithm)
+    })
+}
+
+pub fn solution1(data: &Day20) -> String {
+    match enhance_times(data, 2).lit_pixels() {
+        Some(lit) => lit.to_string(),
+        None => "Infinitely many pixels are lit".to_string(),
+    }
+}
+
+pub fn solution2(data: &Day20) -> String {
+    match enhance_times(data, 50).lit_pixels() {
+        Some(lit) => lit.to_string(),
+        None => "Infinitely many pixels are lit".to_string(),
+    }
+}
+
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&day);
+    let soln2 = solution2(&day);
+
+    return Ok((soln1, soln2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE: &str = include_str!("../examples/day20.txt");
+
+    // algorithm that turns a pixel on exactly when its neighbourhood is all dark, which
+    // makes the infinite background flip on every enhancement
+    fn flipping_algorithm() -> [bool; ALGORITHM_SIZE] {
+        let mut algorithm = [false; ALGORITHM_SIZE];
+        algorithm[0] = true;
+        algorithm
+    }
+
+    #[test]
+    fn test_parse() {
+        let day = parse(EXAMPLE).unwrap();
+        assert_eq!(day.image.width, 5);
+        assert_eq!(day.image.height, 5);
+        assert_eq!(day.image.lit_pixels(), Some(10));
+        assert_eq!(day.algorithm[34], true);
+        assert_eq!(day.algorithm[0], false);
+
+        assert_eq!(
+            parse("..#\n\n#."),
+            Err(Day20Error::InvalidAlgorithmLength(3))
+        );
+        assert_eq!(
+            parse("#.#"),
+            Err(Day20Error::MissingInputData(
+                "Unable to find image enhancement algorithm".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_image() {
+        assert_eq!(
+            parse_image("#.\n.#"),
+            Ok(Image::new(2, 2, vec![true, false, false, true]).unwrap())
+        );
+        assert_eq!(parse_image("#.\n#"), Err(Day20Error::RaggedImage(2, 2, 1)));
+        assert_eq!(parse_image("#x"), Err(Day20Error::InvalidPixel('x')));
+    }
+
+    #[test]
+    fn test_image_enhance() {
+        let day = parse(EXAMPLE).unwrap();
+        let once = day.image.enhance(&day.algorithm);
+        assert_eq!(once.lit_pixels(), Some(24));
+        assert_eq!(once.to_string().lines().count(), 7);
+    }
+
+    #[test]
+    fn test_image_enhance_infinite_background() {
+        let algorithm = flipping_algorithm();
+        let image = Image::new(1, 1, vec![false]).unwrap();
+
+        let once = image.enhance(&algorithm);
+        assert_eq!(once.background, true);
+        assert_eq!(once.lit_pixels(), None);
+        assert_eq!(once.pixel(-100, 100), true);
+
+        let twice = once.enhance(&algorithm);
+        assert_eq!(twice.background, false);
+        assert_eq!(twice.lit_pixels(), Some(0));
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution1(&data), "35");
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution2(&data), "3351");
+    }
+}
@@ -0,0 +1,292 @@
+/*
+Advent of Code 2021 solutions
+Copyright (C) 2021  Alex Bechanko
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use std::collections::HashMap;
+
+const BOARD_SIZE: u32 = 10;
+const DETERMINISTIC_TARGET: u32 = 1000;
+const DIRAC_TARGET: u32 = 21;
+
+// (sum of three rolls of a three sided die, number of universes producing that sum)
+const DIRAC_ROLLS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Day21Error {
+    ParsePlayerFailure(String),
+    InvalidPosition(u32),
+    MissingPlayer(usize),
+}
+
+impl std::fmt::Display for Day21Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day21Error::ParsePlayerFailure(line) => {
+                write!(f, "Failed to parse player starting position: {}", line)
+            }
+            Day21Error::InvalidPosition(pos) => {
+                write!(
+                    f,
+                    "Starting position {} is not between 1 and {}",
+                    pos, BOARD_SIZE
+                )
+            }
+            Day21Error::MissingPlayer(player) => {
+                write!(f, "Missing starting position for player {}", player)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct GameState {
+    positions: [u32; 2],
+    scores: [u32; 2],
+    turn: usize,
+}
+
+impl GameState {
+    pub fn new(player1: u32, player2: u32) -> Option<GameState> {
+        if !(1..=BOARD_SIZE).contains(&player1) || !(1..=BOARD_SIZE).contains(&player2) {
+            return None;
+        }
+
+        Some(GameState {
+            positions: [player1, player2],
+            scores: [0, 0],
+            turn: 0,
+        })
+    }
+
+    pub fn advance(&self, roll: u32) -> GameState {
+        let mut state = *self;
+        let player = self.turn;
+
+        state.positions[player] = (self.positions[player] + roll - 1) % BOARD_SIZE + 1;
+        state.scores[player] += state.positions[player];
+        state.turn = 1 - player;
+
+        return state;
+    }
+
+    pub fn winner(&self, target: u32) -> Option<usize> {
+        self.scores.iter().position(|&score| score >= target)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeterministicDie {
+    next: u32,
+    rolls: u32,
+}
+
+impl DeterministicDie {
+    pub fn new() -> Self {
+        DeterministicDie { next: 1, rolls: 0 }
+    }
+
+    pub fn roll(&mut self) -> u32 {
+        let value = self.next;
+        self.next = self.next % 100 + 1;
+        self.rolls += 1;
+
+        value
+    }
+
+    pub fn rolls(&self) -> u32 {
+        self.rolls
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiracGame {
+    target: u32,
+    cache: HashMap<GameState, [u64; 2]>,
+}
+
+impl DiracGame {
+    pub fn new(target: u32) -> Self {
+        DiracGame {
+            target,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn wins(&mut self, state: GameState) -> [u64; 2] {
+        if let Some(&wins) = self.cache.get(&state) {
+            return wins;
+        }
+
+        let mut wins = [0, 0];
+        for &(roll, universes) in DIRAC_ROLLS.iter() {
+            let next = state.advance(roll);
+            match next.winner(self.target) {
+                Some(player) => wins[player] += universes,
+                None => {
+                    let [p1, p2] = self.wins(next);
+                    wins[0] += p1 * universes;
+                    wins[1] += p2 * universes;
+                }
+            }
+        }
+
+        self.cache.insert(state, wins);
+        return wins;
+    }
+}
+
+pub fn parse_player(line: &str) -> Result<(usize, u32), Day21Error> {
+    let (player, position) = line
+        .trim()
+        .strip_prefix("Player ")
+        .and_then(|rest| rest.split_once(" starting position: "))
+        .ok_or(Day21Error::ParsePlayerFailure(line.to_string()))?;
+
+    let player = player
+        .parse()
+        .ok()
+        .ok_or(Day21Error::ParsePlayerFailure(line.to_string()))?;
+    let position = position
+        .parse()
+        .ok()
+        .ok_or(Day21Error::ParsePlayerFailure(line.to_string()))?;
+
+    return Ok((player, position));
+}
+
+pub fn parse(data: &str) -> Result<GameState, Day21Error> {
+    let mut positions = [None, None];
+    for line in data.lines().filter(|l| !l.trim().is_empty()) {
+        let (player, position) = parse_player(line)?;
+        let slot = player
+            .checked_sub(1)
+            .and_then(|i| positions.get_mut(i))
+            .ok_or(Day21Error::ParsePlayerFailure(line.to_string()))?;
+
+        if !(1..=BOARD_SIZE).contains(&position) {
+            return Err(Day21Error::InvalidPosition(position));
+        }
+        *slot = Some(position);
+    }
+
+    let player1 = positions[0].ok_or(Day21Error::MissingPlayer(1))?;
+    let player2 = positions[1].ok_or(Day21Error::MissingPlayer(2))?;
+
+    GameState::new(player1, player2).ok_or(Day21Error::InvalidPosition(player1))
+}
+
+pub fn solution1(data: &GameState) -> String {
+    let mut die = DeterministicDie::new();
+    let mut state = *data;
+
+    loop {
+        let roll = die.roll() + die.roll() + die.roll();
+        state = state.advance(roll);
+
+        if let Some(winner) = state.winner(DETERMINISTIC_TARGET) {
+            let loser = state.scores[1 - winner];
+            return (loser * die.rolls()).to_string();
+        }
+    }
+}
+
+pub fn solution2(data: &GameState) -> String {
+    let wins = DiracGame::new(DIRAC_TARGET).wins(*data);
+    wins.iter().max().unwrap_or(&0).to_string()
+}
+
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&day);
+    let soln2 = solution2(&day);
+
+    return Ok((soln1, soln2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE: &str = include_str!("../examples/day21.txt");
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(EXAMPLE), Ok(GameState::new(4, 8).unwrap()));
+        assert_eq!(
+            parse("Player 1 starting position: 4"),
+            Err(Day21Error::MissingPlayer(2))
+        );
+        assert_eq!(
+            parse("Player 1 starting position: 11\nPlayer 2 starting position: 8"),
+            Err(Day21Error::InvalidPosition(11))
+        );
+        assert_eq!(
+            parse("Player 3 starting position: 1"),
+            Err(Day21Error::ParsePlayerFailure(
+                "Player 3 starting position: 1".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_game_state_advance() {
+        let state = GameState::new(4, 8).unwrap();
+        let state = state.advance(1 + 2 + 3);
+        assert_eq!(state.positions, [10, 8]);
+        assert_eq!(state.scores, [10, 0]);
+        assert_eq!(state.turn, 1);
+
+        let state = state.advance(4 + 5 + 6);
+        assert_eq!(state.positions, [10, 3]);
+        assert_eq!(state.scores, [10, 3]);
+        assert_eq!(state.turn, 0);
+    }
+
+    #[test]
+    fn test_deterministic_die() {
+        let mut die = DeterministicDie::new();
+        for expected in 1..=100 {
+            assert_eq!(die.roll(), expected);
+        }
+        assert_eq!(die.roll(), 1);
+        assert_eq!(die.rolls(), 101);
+    }
+
+    #[test]
+    fn test_dirac_game_wins() {
+        let mut game = DiracGame::new(DIRAC_TARGET);
+        assert_eq!(
+            game.wins(GameState::new(4, 8).unwrap()),
+            [444356092776315, 341960390180808]
+        );
+
+        // a target reachable on the first move is always won by the first player
+        let mut game = DiracGame::new(1);
+        assert_eq!(game.wins(GameState::new(1, 1).unwrap()), [27, 0]);
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution1(&data), "739785");
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution2(&data), "444356092776315");
+    }
+}
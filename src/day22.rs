@@ -0,0 +1,305 @@
+/*
+Advent of Code 2021 solutions
+Copyright (C) 2021  Alex Bechanko
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Day22Error {
+    ParseStepFailure(String),
+    ParseRangeFailure(String, String),
+    InvalidCuboid(String),
+}
+
+impl std::fmt::Display for Day22Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day22Error::ParseStepFailure(line) => {
+                write!(f, "Failed to parse reboot step: {}", line)
+            }
+            Day22Error::ParseRangeFailure(line, range) => {
+                write!(
+                    f,
+                    "Failed to parse range '{}' in reboot step: {}",
+                    range, line
+                )
+            }
+            Day22Error::InvalidCuboid(line) => {
+                write!(
+                    f,
+                    "Failed to construct cuboid, a range ends before it starts: {}",
+                    line
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cuboid {
+    min: [i64; 3],
+    max: [i64; 3],
+}
+
+impl Cuboid {
+    pub fn new(min: [i64; 3], max: [i64; 3]) -> Option<Cuboid> {
+        if (0..3).any(|axis| min[axis] > max[axis]) {
+            return None;
+        }
+
+        Some(Cuboid { min, max })
+    }
+
+    pub fn volume(&self) -> i64 {
+        (0..3)
+            .map(|axis| self.max[axis] - self.min[axis] + 1)
+            .product()
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut min = [0; 3];
+        let mut max = [0; 3];
+        for axis in 0..3 {
+            min[axis] = self.min[axis].max(other.min[axis]);
+            max[axis] = self.max[axis].min(other.max[axis]);
+        }
+
+        Cuboid::new(min, max)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RebootStep {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+// A reactor stores cuboids with a sign, so the lit volume is the signed sum of their
+// volumes. Every new step cancels its overlap with the existing cuboids by adding the
+// intersections back with the opposite sign, then adds itself if it turns cubes on.
+#[derive(Debug, Clone, Default)]
+pub struct Reactor {
+    cuboids: Vec<(Cuboid, i64)>,
+}
+
+impl Reactor {
+    pub fn new() -> Self {
+        Reactor {
+            cuboids: Vec::new(),
+        }
+    }
+
+    pub fn apply(&mut self, step: &RebootStep) {
+        let overlaps: Vec<(Cuboid, i64)> = self
+            .cuboids
+            .iter()
+            .filter_map(|(cuboid, sign)| {
+                cuboid
+                    .intersection(&step.cuboid)
+                    .map(|overlap| (overlap, -sign))
+            })
+            .collect();
+
+        self.cuboids.extend(overlaps);
+        if step.on {
+            self.cuboids.push((step.cuboid, 1));
+        }
+    }
+
+    pub fn lit(&self) -> i64 {
+        self.cuboids
+            .iter()
+            .map(|(cuboid, sign)| cuboid.volume() * sign)
+            .sum()
+    }
+}
+
+pub fn initialization_region() -> Cuboid {
+    Cuboid {
+        min: [-50; 3],
+        max: [50; 3],
+    }
+}
+
+pub fn parse_range(line: &str, range: &str, axis: char) -> Result<(i64, i64), Day22Error> {
+    let failure = || Day22Error::ParseRangeFailure(line.to_string(), range.to_string());
+
+    let (name, bounds) = range.trim().split_once('=').ok_or_else(failure)?;
+    if name != axis.to_string() {
+        return Err(failure());
+    }
+
+    let (start, end) = bounds.split_once("..").ok_or_else(failure)?;
+    let start = start.parse().ok().ok_or_else(failure)?;
+    let end = end.parse().ok().ok_or_else(failure)?;
+
+    return Ok((start, end));
+}
+
+pub fn parse_step(line: &str) -> Result<RebootStep, Day22Error> {
+    let (state, ranges) = line
+        .trim()
+        .split_once(' ')
+        .ok_or(Day22Error::ParseStepFailure(line.to_string()))?;
+
+    let on = match state {
+        "on" => true,
+        "off" => false,
+        _ => return Err(Day22Error::ParseStepFailure(line.to_string())),
+    };
+
+    let ranges = ranges.split(',').collect::<Vec<&str>>();
+    if ranges.len() != 3 {
+        return Err(Day22Error::ParseStepFailure(line.to_string()));
+    }
+
+    let mut min = [0; 3];
+    let mut max = [0; 3];
+    for (axis, (range, name)) in ranges.iter().zip(['x', 'y', 'z']).enumerate() {
+        let (start, end) = parse_range(line, range, name)?;
+        min[axis] = start;
+        max[axis] = end;
+    }
+
+    let cuboid = Cuboid::new(min, max).ok_or(Day22Error::InvalidCuboid(line.to_string()))?;
+
+    return Ok(RebootStep { on, cuboid });
+}
+
+pub fn parse(data: &str) -> Result<Vec<RebootStep>, Day22Error> {
+    data.lines()
+        .map(parse_step)
+        .collect::<Result<Vec<RebootStep>, Day22Error>>()
+}
+
+pub fn solution1(data: &[RebootStep]) -> String {
+    let region = initialization_region();
+    let mut reactor = Reactor::new();
+
+    for step in data.iter() {
+        if let Some(cuboid) = step.cuboid.intersection(&region) {
+            reactor.apply(&RebootStep {
+                on: step.on,
+                cuboid,
+            });
+        }
+    }
+
+    reactor.lit().to_string()
+}
+
+pub fn solution2(data: &[RebootStep]) -> String {
+    let mut reactor = Reactor::new();
+    for step in data.iter() {
+        reactor.apply(step);
+    }
+
+    reactor.lit().to_string()
+}
+
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&day);
+    let soln2 = solution2(&day);
+
+    return Ok((soln1, soln2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE: &str = include_str!("../examples/day22.txt");
+
+    #[test]
+    fn test_parse_step() {
+        assert_eq!(
+            parse_step("on x=-20..26,y=-36..17,z=-47..7"),
+            Ok(RebootStep {
+                on: true,
+                cuboid: Cuboid {
+                    min: [-20, -36, -47],
+                    max: [26, 17, 7]
+                }
+            })
+        );
+        assert_eq!(
+            parse_step("toggle x=1..2,y=1..2,z=1..2"),
+            Err(Day22Error::ParseStepFailure(
+                "toggle x=1..2,y=1..2,z=1..2".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_step("off x=1..2,z=1..2,y=1..2"),
+            Err(Day22Error::ParseRangeFailure(
+                "off x=1..2,z=1..2,y=1..2".to_string(),
+                "z=1..2".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_step("on x=2..1,y=1..2,z=1..2"),
+            Err(Day22Error::InvalidCuboid(
+                "on x=2..1,y=1..2,z=1..2".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_cuboid_volume() {
+        assert_eq!(Cuboid::new([0; 3], [0; 3]).unwrap().volume(), 1);
+        assert_eq!(Cuboid::new([10; 3], [12; 3]).unwrap().volume(), 27);
+        assert_eq!(Cuboid::new([-1, 0, 0], [1, 1, 0]).unwrap().volume(), 6);
+    }
+
+    #[test]
+    fn test_cuboid_intersection() {
+        let a = Cuboid::new([10; 3], [12; 3]).unwrap();
+        let b = Cuboid::new([11; 3], [13; 3]).unwrap();
+        let c = Cuboid::new([13; 3], [14; 3]).unwrap();
+
+        assert_eq!(a.intersection(&b), Cuboid::new([11; 3], [12; 3]));
+        assert_eq!(b.intersection(&c), Cuboid::new([13; 3], [13; 3]));
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn test_reactor() {
+        let steps = parse(EXAMPLE).unwrap();
+        let mut reactor = Reactor::new();
+        let expected = vec![27, 46, 38, 39];
+
+        for (step, lit) in steps.iter().zip(expected) {
+            reactor.apply(step);
+            assert_eq!(reactor.lit(), lit, "after step {:?}", step);
+        }
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution1(&data), "39");
+
+        let data = parse("on x=-60..60,y=0..0,z=0..0").unwrap();
+        assert_eq!(solution1(&data), "101");
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution2(&data), "39");
+
+        let data = parse("on x=-60..60,y=0..0,z=0..0\noff x=0..100000,y=0..0,z=0..0").unwrap();
+        assert_eq!(solution2(&data), "60");
+    }
+}
@@ -0,0 +1,459 @@
+/*
+Advent of Code 2021 solutions
+Copyright (C) 2021  Alex Bechanko
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+const HALLWAY_SIZE: usize = 11;
+const NUM_ROOMS: usize = 4;
+
+// rows inserted between the two rows of the folded diagram for part 2
+const UNFOLDED_ROWS: [[Amphipod; NUM_ROOMS]; 2] = [
+    [
+        Amphipod::Desert,
+        Amphipod::Copper,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+    ],
+    [
+        Amphipod::Desert,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+        Amphipod::Copper,
+    ],
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Day23Error {
+    MissingInputData(String),
+    InvalidAmphipod(char),
+    InvalidRoomRow(String),
+}
+
+impl std::fmt::Display for Day23Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day23Error::MissingInputData(why) => {
+                write!(f, "Missing input data: {}", why)
+            }
+            Day23Error::InvalidAmphipod(c) => {
+                write!(f, "Failed to parse '{}' as an amphipod", c)
+            }
+            Day23Error::InvalidRoomRow(row) => {
+                write!(f, "Failed to parse burrow row: {}", row)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    pub fn energy(&self) -> u64 {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+
+    pub fn room(&self) -> usize {
+        match self {
+            Amphipod::Amber => 0,
+            Amphipod::Bronze => 1,
+            Amphipod::Copper => 2,
+            Amphipod::Desert => 3,
+        }
+    }
+}
+
+impl TryFrom<char> for Amphipod {
+    type Error = Day23Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'A' => Ok(Amphipod::Amber),
+            'B' => Ok(Amphipod::Bronze),
+            'C' => Ok(Amphipod::Copper),
+            'D' => Ok(Amphipod::Desert),
+            _ => Err(Day23Error::InvalidAmphipod(c)),
+        }
+    }
+}
+
+impl std::fmt::Display for Amphipod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let c = match self {
+            Amphipod::Amber => 'A',
+            Amphipod::Bronze => 'B',
+            Amphipod::Copper => 'C',
+            Amphipod::Desert => 'D',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+// Rooms are stored top to bottom, so index 0 of a room is the slot next to the hallway.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY_SIZE],
+    rooms: [Vec<Option<Amphipod>>; NUM_ROOMS],
+}
+
+pub fn room_entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+pub fn is_entrance(x: usize) -> bool {
+    (0..NUM_ROOMS).any(|room| room_entrance(room) == x)
+}
+
+impl Burrow {
+    pub fn new(rows: &[[Amphipod; NUM_ROOMS]]) -> Burrow {
+        let rooms = [0, 1, 2, 3].map(|room| rows.iter().map(|row| Some(row[room])).collect());
+
+        Burrow {
+            hallway: [None; HALLWAY_SIZE],
+            rooms,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.rooms[0].len()
+    }
+
+    pub fn unfold(&self, rows: &[[Amphipod; NUM_ROOMS]]) -> Burrow {
+        let mut burrow = self.clone();
+        for (room, slots) in burrow.rooms.iter_mut().enumerate() {
+            let extra = rows.iter().map(|row| Some(row[room]));
+            slots.splice(1..1, extra);
+        }
+
+        burrow
+    }
+
+    pub fn is_organized(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(room, slots)| {
+            slots
+                .iter()
+                .all(|slot| slot.map(|a| a.room()) == Some(room))
+        })
+    }
+
+    // a room accepts amphipods once only its own kind is left inside it
+    fn is_settled(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .flatten()
+            .all(|amphipod| amphipod.room() == room)
+    }
+
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let (lo, hi) = if from < to {
+            (from + 1, to)
+        } else {
+            (to, from - 1)
+        };
+
+        self.hallway[lo..=hi].iter().all(|slot| slot.is_none())
+    }
+
+    pub fn moves(&self) -> Vec<(Burrow, u64)> {
+        let mut moves = Vec::new();
+
+        // amphipods in the hallway can only move into their own room
+        for (x, slot) in self.hallway.iter().enumerate() {
+            let amphipod = match slot {
+                Some(amphipod) => *amphipod,
+                None => continue,
+            };
+
+            let room = amphipod.room();
+            let entrance = room_entrance(room);
+            if !self.is_settled(room) || !self.hallway_clear(x, entrance) {
+                continue;
+            }
+
+            if let Some(depth) = self.rooms[room].iter().rposition(|slot| slot.is_none()) {
+                let steps = x.abs_diff(entrance) + depth + 1;
+                let mut burrow = self.clone();
+                burrow.hallway[x] = None;
+                burrow.rooms[room][depth] = Some(amphipod);
+                moves.push((burrow, steps as u64 * amphipod.energy()));
+            }
+        }
+
+        // the top amphipod of an unsettled room can move to any reachable hallway stop
+        for room in 0..NUM_ROOMS {
+            if self.is_settled(room) {
+                continue;
+            }
+
+            let depth = match self.rooms[room].iter().position(|slot| slot.is_some()) {
+                Some(depth) => depth,
+                None => continue,
+            };
+            let amphipod = self.rooms[room][depth].unwrap();
+            let entrance = room_entrance(room);
+
+            for x in (0..HALLWAY_SIZE).filter(|&x| !is_entrance(x)) {
+                if !self.hallway_clear(entrance, x) || self.hallway[x].is_some() {
+                    continue;
+                }
+
+                let steps = x.abs_diff(entrance) + depth + 1;
+                let mut burrow = self.clone();
+                burrow.rooms[room][depth] = None;
+                burrow.hallway[x] = Some(amphipod);
+                moves.push((burrow, steps as u64 * amphipod.energy()));
+            }
+        }
+
+        moves
+    }
+
+    pub fn minimum_energy(&self) -> Option<u64> {
+        let mut best: HashMap<Burrow, u64> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        best.insert(self.clone(), 0);
+        queue.push(Reverse((0, self.clone())));
+
+        while let Some(Reverse((energy, burrow))) = queue.pop() {
+            if burrow.is_organized() {
+                return Some(energy);
+            }
+
+            if best.get(&burrow).is_some_and(|&e| e < energy) {
+                continue;
+            }
+
+            for (next, cost) in burrow.moves() {
+                let next_energy = energy + cost;
+                if best.get(&next).is_none_or(|&e| next_energy < e) {
+                    best.insert(next.clone(), next_energy);
+                    queue.push(Reverse((next_energy, next)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl std::fmt::Display for Burrow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let cell = |slot: &Option<Amphipod>| slot.map_or(".".to_string(), |a| a.to_string());
+
+        writeln!(f, "#############")?;
+        let hallway: String = self.hallway.iter().map(cell).collect();
+        writeln!(f, "#{}#", hallway)?;
+
+        for depth in 0..self.depth() {
+            let row: Vec<String> = self.rooms.iter().map(|r| cell(&r[depth])).collect();
+            if depth == 0 {
+                writeln!(f, "###{}###", row.join("#"))?;
+            } else {
+                writeln!(f, "  #{}#", row.join("#"))?;
+            }
+        }
+
+        write!(f, "  #########")
+    }
+}
+
+pub fn parse_room_row(line: &str) -> Result<[Amphipod; NUM_ROOMS], Day23Error> {
+    let amphipods = line
+        .chars()
+        .filter(|&c| c != '#' && c != ' ')
+        .map(Amphipod::try_from)
+        .collect::<Result<Vec<Amphipod>, Day23Error>>()?;
+
+    amphipods
+        .try_into()
+        .map_err(|_| Day23Error::InvalidRoomRow(line.to_string()))
+}
+
+pub fn parse(data: &str) -> Result<Burrow, Day23Error> {
+    let rows = data
+        .lines()
+        .skip(2)
+        .filter(|line| line.chars().any(|c| c != '#' && c != ' '))
+        .map(parse_room_row)
+        .collect::<Result<Vec<[Amphipod; NUM_ROOMS]>, Day23Error>>()?;
+
+    if rows.is_empty() {
+        return Err(Day23Error::MissingInputData(
+            "Unable to find any room rows".to_string(),
+        ));
+    }
+
+    return Ok(Burrow::new(&rows));
+}
+
+pub fn solution1(data: &Burrow) -> String {
+    match data.minimum_energy() {
+        Some(energy) => energy.to_string(),
+        None => "No solution found".to_string(),
+    }
+}
+
+pub fn solution2(data: &Burrow) -> String {
+    match data.unfold(&UNFOLDED_ROWS).minimum_energy() {
+        Some(energy) => energy.to_string(),
+        None => "No solution found".to_string(),
+    }
+}
+
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&day);
+    let soln2 = solution2(&day);
+
+    return Ok((soln1, soln2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE: &str = include_str!("../examples/day23.txt");
+
+    #[test]
+    fn test_parse() {
+        let burrow = parse(EXAMPLE).unwrap();
+        assert_eq!(burrow.depth(), 2);
+        assert_eq!(burrow.to_string(), EXAMPLE);
+
+        assert_eq!(
+            parse("#############\n#...........#\n###B#C#B#E###"),
+            Err(Day23Error::InvalidAmphipod('E'))
+        );
+        assert_eq!(
+            parse("#############\n#...........#\n###B#C#B###"),
+            Err(Day23Error::InvalidRoomRow("###B#C#B###".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_burrow_unfold() {
+        let burrow = parse(EXAMPLE).unwrap().unfold(&UNFOLDED_ROWS);
+        assert_eq!(burrow.depth(), 4);
+        assert_eq!(
+            burrow.rooms[0],
+            vec![
+                Some(Amphipod::Bronze),
+                Some(Amphipod::Desert),
+                Some(Amphipod::Desert),
+                Some(Amphipod::Amber)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_burrow_moves() {
+        // only the top amphipod of each room can leave, and only to the seven hallway stops
+        let burrow = parse(EXAMPLE).unwrap();
+        assert_eq!(burrow.moves().len(), 4 * 7);
+
+        // an amphipod that is already home above a stranger still has to leave to let it out
+        let burrow = Burrow::new(&[
+            [
+                Amphipod::Amber,
+                Amphipod::Bronze,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ],
+            [
+                Amphipod::Bronze,
+                Amphipod::Amber,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ],
+        ]);
+        let moves = burrow.moves();
+        assert_eq!(moves.len(), 2 * 7);
+        assert!(moves
+            .iter()
+            .any(|(b, _)| b.rooms[0] == vec![None, Some(Amphipod::Bronze)]
+                && b.hallway.contains(&Some(Amphipod::Amber))));
+
+        // an organized burrow has no moves left
+        let mut burrow = Burrow::new(&[
+            [
+                Amphipod::Amber,
+                Amphipod::Bronze,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ],
+            [
+                Amphipod::Amber,
+                Amphipod::Bronze,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ],
+        ]);
+        assert_eq!(burrow.is_organized(), true);
+        assert_eq!(burrow.moves().len(), 0);
+
+        burrow.rooms[0][0] = None;
+        burrow.hallway[10] = Some(Amphipod::Amber);
+        assert_eq!(burrow.moves().len(), 1);
+        assert_eq!(burrow.moves()[0].1, 9);
+    }
+
+    #[test]
+    fn test_burrow_minimum_energy() {
+        let burrow = Burrow::new(&[
+            [
+                Amphipod::Bronze,
+                Amphipod::Amber,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ],
+            [
+                Amphipod::Amber,
+                Amphipod::Bronze,
+                Amphipod::Copper,
+                Amphipod::Desert,
+            ],
+        ]);
+
+        // B waits at x=3 (20), A waits at x=5 (2), then B (20) and A (4) go home
+        assert_eq!(burrow.minimum_energy(), Some(46));
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution1(&data), "12521");
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution2(&data), "44169");
+    }
+}
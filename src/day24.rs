@@ -0,0 +1,521 @@
+/*
+Advent of Code 2021 solutions
+Copyright (C) 2021  Alex Bechanko
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+const MODEL_NUMBER_DIGITS: usize = 14;
+const BLOCK_SIZE: usize = 18;
+
+// offsets of the constants that differ between the blocks of a MONAD program
+const BLOCK_DIVISOR: usize = 4;
+const BLOCK_CHECK: usize = 5;
+const BLOCK_OFFSET: usize = 15;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Day24Error {
+    ParseInstructionFailure(String),
+    MissingInput,
+    DivideByZero(usize),
+    InvalidModulo(usize),
+    Overflow(usize),
+    InvalidModelNumber(u64),
+    UnexpectedBlock(usize),
+    NoModelNumber,
+}
+
+impl std::fmt::Display for Day24Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day24Error::ParseInstructionFailure(line) => {
+                write!(f, "Failed to parse instruction: {}", line)
+            }
+            Day24Error::MissingInput => {
+                write!(f, "Program read more input than was provided")
+            }
+            Day24Error::DivideByZero(pc) => {
+                write!(f, "Division by zero at instruction {}", pc)
+            }
+            Day24Error::InvalidModulo(pc) => {
+                write!(f, "Invalid modulo operands at instruction {}", pc)
+            }
+            Day24Error::Overflow(pc) => {
+                write!(f, "Arithmetic overflow at instruction {}", pc)
+            }
+            Day24Error::InvalidModelNumber(n) => {
+                write!(
+                    f,
+                    "{} is not a {} digit model number without zeros",
+                    n, MODEL_NUMBER_DIGITS
+                )
+            }
+            Day24Error::UnexpectedBlock(block) => {
+                write!(
+                    f,
+                    "Block {} does not have the expected MONAD structure",
+                    block
+                )
+            }
+            Day24Error::NoModelNumber => {
+                write!(f, "No model number is accepted by the program")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Register {
+    fn index(&self) -> usize {
+        match self {
+            Register::W => 0,
+            Register::X => 1,
+            Register::Y => 2,
+            Register::Z => 3,
+        }
+    }
+}
+
+impl std::str::FromStr for Register {
+    type Err = Day24Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Register::W),
+            "x" => Ok(Register::X),
+            "y" => Ok(Register::Y),
+            "z" => Ok(Register::Z),
+            _ => Err(Day24Error::ParseInstructionFailure(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operand {
+    Register(Register),
+    Literal(i64),
+}
+
+impl std::str::FromStr for Operand {
+    type Err = Day24Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<i64>() {
+            Ok(n) => Ok(Operand::Literal(n)),
+            Err(_) => s.parse().map(Operand::Register),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl std::str::FromStr for Instruction {
+    type Err = Day24Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let failure = || Day24Error::ParseInstructionFailure(s.to_string());
+        let parts = s.split_whitespace().collect::<Vec<&str>>();
+
+        let register: Register = parts
+            .get(1)
+            .ok_or_else(failure)?
+            .parse()
+            .map_err(|_| failure())?;
+
+        if parts[0] == "inp" {
+            return match parts.len() {
+                2 => Ok(Instruction::Inp(register)),
+                _ => Err(failure()),
+            };
+        }
+
+        if parts.len() != 3 {
+            return Err(failure());
+        }
+        let operand: Operand = parts[2].parse().map_err(|_| failure())?;
+
+        match parts[0] {
+            "add" => Ok(Instruction::Add(register, operand)),
+            "mul" => Ok(Instruction::Mul(register, operand)),
+            "div" => Ok(Instruction::Div(register, operand)),
+            "mod" => Ok(Instruction::Mod(register, operand)),
+            "eql" => Ok(Instruction::Eql(register, operand)),
+            _ => Err(failure()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    pub fn new() -> Self {
+        Alu { registers: [0; 4] }
+    }
+
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register.index()]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Literal(n) => n,
+        }
+    }
+
+    pub fn run<I: Iterator<Item = i64>>(
+        &mut self,
+        program: &[Instruction],
+        input: &mut I,
+    ) -> Result<(), Day24Error> {
+        for (pc, &instruction) in program.iter().enumerate() {
+            let (register, result) = match instruction {
+                Instruction::Inp(a) => (a, input.next().ok_or(Day24Error::MissingInput)?),
+                Instruction::Add(a, b) => (
+                    a,
+                    self.get(a)
+                        .checked_add(self.value(b))
+                        .ok_or(Day24Error::Overflow(pc))?,
+                ),
+                Instruction::Mul(a, b) => (
+                    a,
+                    self.get(a)
+                        .checked_mul(self.value(b))
+                        .ok_or(Day24Error::Overflow(pc))?,
+                ),
+                Instruction::Div(a, b) => {
+                    let b = self.value(b);
+                    if b == 0 {
+                        return Err(Day24Error::DivideByZero(pc));
+                    }
+                    (
+                        a,
+                        self.get(a).checked_div(b).ok_or(Day24Error::Overflow(pc))?,
+                    )
+                }
+                Instruction::Mod(a, b) => {
+                    let (a_value, b) = (self.get(a), self.value(b));
+                    if a_value < 0 || b <= 0 {
+                        return Err(Day24Error::InvalidModulo(pc));
+                    }
+                    (a, a_value % b)
+                }
+                Instruction::Eql(a, b) => (a, (self.get(a) == self.value(b)) as i64),
+            };
+
+            self.registers[register.index()] = result;
+        }
+
+        Ok(())
+    }
+}
+
+// Each of the fourteen MONAD blocks either pushes `digit + offset` onto a base 26 stack
+// held in z, or pops a value and only avoids pushing again if `popped + check == digit`.
+// z ends at zero exactly when every pop block matches the push block it pairs with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Block {
+    pop: bool,
+    check: i64,
+    offset: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Monad {
+    program: Vec<Instruction>,
+}
+
+pub fn model_number_digits(model_number: u64) -> Result<Vec<i64>, Day24Error> {
+    let digits = model_number
+        .to_string()
+        .chars()
+        .map(|c| c as i64 - '0' as i64)
+        .collect::<Vec<i64>>();
+
+    if digits.len() != MODEL_NUMBER_DIGITS || digits.contains(&0) {
+        return Err(Day24Error::InvalidModelNumber(model_number));
+    }
+
+    return Ok(digits);
+}
+
+impl Monad {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Monad { program }
+    }
+
+    pub fn validate(&self, model_number: u64) -> Result<bool, Day24Error> {
+        let digits = model_number_digits(model_number)?;
+        let mut alu = Alu::new();
+        alu.run(&self.program, &mut digits.into_iter())?;
+
+        return Ok(alu.get(Register::Z) == 0);
+    }
+
+    pub fn blocks(&self) -> Result<Vec<Block>, Day24Error> {
+        if self.program.len() != BLOCK_SIZE * MODEL_NUMBER_DIGITS {
+            return Err(Day24Error::UnexpectedBlock(self.program.len() / BLOCK_SIZE));
+        }
+
+        self.program
+            .chunks(BLOCK_SIZE)
+            .enumerate()
+            .map(|(i, block)| {
+                let divisor = block[BLOCK_DIVISOR];
+                let check = block[BLOCK_CHECK];
+                let offset = block[BLOCK_OFFSET];
+
+                match (divisor, check, offset) {
+                    (
+                        Instruction::Div(Register::Z, Operand::Literal(divisor)),
+                        Instruction::Add(Register::X, Operand::Literal(check)),
+                        Instruction::Add(Register::Y, Operand::Literal(offset)),
+                    ) if divisor == 1 || divisor == 26 => Ok(Block {
+                        pop: divisor == 26,
+                        check,
+                        offset,
+                    }),
+                    _ => Err(Day24Error::UnexpectedBlock(i)),
+                }
+            })
+            .collect()
+    }
+
+    // pairs of (push block, pop block, difference) where digit[pop] = digit[push] + difference
+    pub fn constraints(&self) -> Result<Vec<(usize, usize, i64)>, Day24Error> {
+        let mut stack = Vec::new();
+        let mut constraints = Vec::new();
+
+        for (i, block) in self.blocks()?.into_iter().enumerate() {
+            if block.pop {
+                let (j, offset) = stack.pop().ok_or(Day24Error::UnexpectedBlock(i))?;
+                constraints.push((j, i, offset + block.check));
+            } else {
+                stack.push((i, block.offset));
+            }
+        }
+
+        if !stack.is_empty() {
+            return Err(Day24Error::NoModelNumber);
+        }
+
+        return Ok(constraints);
+    }
+
+    fn model_number(&self, largest: bool) -> Result<u64, Day24Error> {
+        let mut digits = [0; MODEL_NUMBER_DIGITS];
+
+        for (push, pop, difference) in self.constraints()? {
+            if difference.abs() > 8 {
+                return Err(Day24Error::NoModelNumber);
+            }
+
+            let push_digit = if largest {
+                9.min(9 - difference)
+            } else {
+                1.max(1 - difference)
+            };
+            digits[push] = push_digit;
+            digits[pop] = push_digit + difference;
+        }
+
+        let model_number = digits.iter().fold(0, |n, &d| n * 10 + d as u64);
+        return Ok(model_number);
+    }
+
+    pub fn largest_model_number(&self) -> Result<u64, Day24Error> {
+        self.model_number(true)
+    }
+
+    pub fn smallest_model_number(&self) -> Result<u64, Day24Error> {
+        self.model_number(false)
+    }
+}
+
+pub fn parse(data: &str) -> Result<Monad, Day24Error> {
+    let program = data
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().parse())
+        .collect::<Result<Vec<Instruction>, Day24Error>>()?;
+
+    return Ok(Monad::new(program));
+}
+
+pub fn solution1(data: &Monad) -> Result<String, Day24Error> {
+    let model_number = data.largest_model_number()?;
+    match data.validate(model_number)? {
+        true => Ok(model_number.to_string()),
+        false => Err(Day24Error::NoModelNumber),
+    }
+}
+
+pub fn solution2(data: &Monad) -> Result<String, Day24Error> {
+    let model_number = data.smallest_model_number()?;
+    match data.validate(model_number)? {
+        true => Ok(model_number.to_string()),
+        false => Err(Day24Error::NoModelNumber),
+    }
+}
+
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&day).map_err(|e| e.to_string())?;
+    let soln2 = solution2(&day).map_err(|e| e.to_string())?;
+
+    return Ok((soln1, soln2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE: &str = include_str!("../examples/day24.txt");
+
+    #[test]
+    fn test_parse_instruction() {
+        assert_eq!(
+            "inp w".parse::<Instruction>(),
+            Ok(Instruction::Inp(Register::W))
+        );
+        assert_eq!(
+            "add z -14".parse::<Instruction>(),
+            Ok(Instruction::Add(Register::Z, Operand::Literal(-14)))
+        );
+        assert_eq!(
+            "eql x w".parse::<Instruction>(),
+            Ok(Instruction::Eql(
+                Register::X,
+                Operand::Register(Register::W)
+            ))
+        );
+        assert_eq!(
+            "sub x 1".parse::<Instruction>(),
+            Err(Day24Error::ParseInstructionFailure("sub x 1".to_string()))
+        );
+        assert_eq!(
+            "add q 1".parse::<Instruction>(),
+            Err(Day24Error::ParseInstructionFailure("add q 1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_alu_run() {
+        // binary conversion program from the puzzle description
+        let program = parse(
+            "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2",
+        )
+        .unwrap()
+        .program;
+
+        let mut alu = Alu::new();
+        alu.run(&program, &mut vec![11].into_iter()).unwrap();
+        assert_eq!(alu.registers, [1, 0, 1, 1]);
+
+        let mut alu = Alu::new();
+        assert_eq!(
+            alu.run(&program, &mut std::iter::empty()),
+            Err(Day24Error::MissingInput)
+        );
+
+        let program = parse("inp x\ndiv x 0").unwrap().program;
+        assert_eq!(
+            Alu::new().run(&program, &mut vec![1].into_iter()),
+            Err(Day24Error::DivideByZero(1))
+        );
+
+        // repeated squaring overflows a 64 bit register
+        let program = parse("inp x\nmul x x\nmul x x").unwrap().program;
+        assert_eq!(
+            Alu::new().run(&program, &mut vec![1 << 20].into_iter()),
+            Err(Day24Error::Overflow(2))
+        );
+
+        let program = parse("inp x\nadd x 1").unwrap().program;
+        assert_eq!(
+            Alu::new().run(&program, &mut vec![i64::MAX].into_iter()),
+            Err(Day24Error::Overflow(1))
+        );
+
+        let program = parse("inp x\ndiv x -1").unwrap().program;
+        assert_eq!(
+            Alu::new().run(&program, &mut vec![i64::MIN].into_iter()),
+            Err(Day24Error::Overflow(1))
+        );
+    }
+
+    #[test]
+    fn test_monad_validate() {
+        let monad = parse(EXAMPLE).unwrap();
+        assert_eq!(monad.validate(99394899891971), Ok(true));
+        assert_eq!(monad.validate(99394899891972), Ok(false));
+        assert_eq!(
+            monad.validate(12345),
+            Err(Day24Error::InvalidModelNumber(12345))
+        );
+        assert_eq!(
+            monad.validate(10000000000000),
+            Err(Day24Error::InvalidModelNumber(10000000000000))
+        );
+    }
+
+    #[test]
+    fn test_monad_constraints() {
+        let monad = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            monad.constraints(),
+            Ok(vec![
+                (2, 3, 6),
+                (5, 6, 1),
+                (4, 7, 5),
+                (1, 8, -1),
+                (10, 11, 8),
+                (9, 12, -2),
+                (0, 13, -8)
+            ])
+        );
+
+        let truncated = Monad::new(monad.program[..BLOCK_SIZE].to_vec());
+        assert_eq!(truncated.constraints(), Err(Day24Error::UnexpectedBlock(1)));
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution1(&data), Ok("99394899891971".to_string()));
+    }
+
+    #[test]
+    fn test_solution2() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution2(&data), Ok("92171126131911".to_string()));
+    }
+}
@@ -0,0 +1,248 @@
+/*
+Advent of Code 2021 solutions
+Copyright (C) 2021  Alex Bechanko
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Day25Error {
+    EmptyData,
+    InvalidCell(char),
+    RaggedRow(usize, usize, usize),
+    NeverSettles(usize),
+}
+
+impl std::fmt::Display for Day25Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Day25Error::EmptyData => {
+                write!(f, "Failed to parse empty data")
+            }
+            Day25Error::InvalidCell(c) => {
+                write!(f, "Failed to parse '{}' as a sea cucumber", c)
+            }
+            Day25Error::RaggedRow(row, expected, actual) => {
+                write!(
+                    f,
+                    "Row {} has {} locations, expected {}",
+                    row, actual, expected
+                )
+            }
+            Day25Error::NeverSettles(steps) => {
+                write!(
+                    f,
+                    "The sea cucumbers repeat a previous position after {} steps and never stop moving",
+                    steps
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = Day25Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Cell::Empty),
+            '>' => Ok(Cell::East),
+            'v' => Ok(Cell::South),
+            _ => Err(Day25Error::InvalidCell(c)),
+        }
+    }
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SeaFloor {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl SeaFloor {
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    // every cucumber in the herd decides whether to move before any of them do
+    fn step_herd(&mut self, herd: Cell) -> usize {
+        let moves: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.cells[self.index(x, y)] == herd)
+            .map(|(x, y)| {
+                let (nx, ny) = match herd {
+                    Cell::East => ((x + 1) % self.width, y),
+                    _ => (x, (y + 1) % self.height),
+                };
+                (self.index(x, y), self.index(nx, ny))
+            })
+            .filter(|&(_, to)| self.cells[to] == Cell::Empty)
+            .collect();
+
+        for &(from, to) in moves.iter() {
+            self.cells[from] = Cell::Empty;
+            self.cells[to] = herd;
+        }
+
+        moves.len()
+    }
+
+    pub fn step(&mut self) -> usize {
+        self.step_herd(Cell::East) + self.step_herd(Cell::South)
+    }
+}
+
+impl std::fmt::Display for SeaFloor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (y, row) in self.cells.chunks(self.width).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(data: &str) -> Result<SeaFloor, Day25Error> {
+    let rows = data
+        .lines()
+        .map(|line| {
+            line.trim()
+                .chars()
+                .map(Cell::try_from)
+                .collect::<Result<Vec<Cell>, Day25Error>>()
+        })
+        .collect::<Result<Vec<Vec<Cell>>, Day25Error>>()?;
+
+    let width = rows.first().map(|r| r.len()).ok_or(Day25Error::EmptyData)?;
+    if width == 0 {
+        return Err(Day25Error::EmptyData);
+    }
+
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+        return Err(Day25Error::RaggedRow(i + 1, width, row.len()));
+    }
+
+    return Ok(SeaFloor {
+        width,
+        height: rows.len(),
+        cells: rows.into_iter().flatten().collect(),
+    });
+}
+
+// A herd that wraps around an otherwise empty row or column moves forever, which shows up
+// as the floor returning to a position it has already been in.
+pub fn solution1(data: &SeaFloor) -> Result<String, Day25Error> {
+    let mut floor = data.clone();
+    let mut seen = std::collections::HashSet::from([floor.cells.clone()]);
+    let mut steps = 1;
+
+    while floor.step() > 0 {
+        if !seen.insert(floor.cells.clone()) {
+            return Err(Day25Error::NeverSettles(steps));
+        }
+        steps += 1;
+    }
+
+    Ok(steps.to_string())
+}
+
+pub fn solution2(_data: &SeaFloor) -> String {
+    "Day 25 has no second puzzle".to_string()
+}
+
+pub fn solutions(data: &str) -> Result<(String, String), String> {
+    let day = parse(data).map_err(|e| e.to_string())?;
+    let soln1 = solution1(&day).map_err(|e| e.to_string())?;
+    let soln2 = solution2(&day);
+
+    return Ok((soln1, soln2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE: &str = include_str!("../examples/day25.txt");
+
+    #[test]
+    fn test_parse() {
+        let floor = parse(EXAMPLE).unwrap();
+        assert_eq!(floor.width, 10);
+        assert_eq!(floor.height, 9);
+        assert_eq!(floor.to_string(), EXAMPLE);
+
+        assert_eq!(parse(""), Err(Day25Error::EmptyData));
+        assert_eq!(parse(">.\n."), Err(Day25Error::RaggedRow(2, 2, 1)));
+        assert_eq!(parse(">x"), Err(Day25Error::InvalidCell('x')));
+    }
+
+    #[test]
+    fn test_sea_floor_step() {
+        let mut floor = parse("...>>>>>...").unwrap();
+        assert_eq!(floor.step(), 1);
+        assert_eq!(floor.to_string(), "...>>>>.>..");
+        assert_eq!(floor.step(), 2);
+        assert_eq!(floor.to_string(), "...>>>.>.>.");
+
+        // the east facing herd moves first, freeing a space for the south facing herd
+        let mut floor = parse("..........\n.>v....v..\n.......>..\n..........").unwrap();
+        assert_eq!(floor.step(), 3);
+        assert_eq!(
+            floor.to_string(),
+            "..........\n.>........\n..v....v>.\n.........."
+        );
+
+        let mut floor = parse(">v\nv>").unwrap();
+        assert_eq!(floor.step(), 0);
+    }
+
+    #[test]
+    fn test_solution1() {
+        let data = parse(EXAMPLE).unwrap();
+        assert_eq!(solution1(&data), Ok("58".to_string()));
+
+        assert_eq!(
+            solution1(&parse(">.").unwrap()),
+            Err(Day25Error::NeverSettles(2))
+        );
+        assert_eq!(
+            solution1(&parse(">.\n.v").unwrap()),
+            Err(Day25Error::NeverSettles(3))
+        );
+    }
+}
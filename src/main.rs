@@ -15,15 +15,30 @@ GNU General Public License for more details.
 You should have received a copy of the GNU General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
+#![allow(clippy::needless_return, clippy::bool_assert_comparison)]
+
 mod day01;
 mod day02;
 mod day03;
 mod day04;
 mod day05;
 mod day06;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
 
 use std::io::Read;
-use std::usize;
+
+type DaySolution = dyn Fn(&str) -> Result<(String, String), String>;
+pub type PartSolution = Result<String, String>;
+
+// exit statuses for when the application fails, and for when it runs but a part of the
+// day's puzzle cannot be solved; clap already exits with 2 on usage errors
+const ERROR_STATUS: i32 = 1;
+const PART_FAILURE_STATUS: i32 = 3;
 
 #[derive(Debug)]
 enum ApplicationError {
@@ -31,28 +46,174 @@ enum ApplicationError {
     InvalidDataFile(String),
     OutOfRangeDay(usize),
     SolutionParseError(String),
+    InvalidWindow,
+    InvalidThreshold,
+    InvalidMode(String),
+    InvalidTolerance,
+    InvalidTrajectory,
+    InvalidTarget,
+    InvalidCriteria(String),
+    InvalidWinRule(String),
+    UnsupportedOption(String, usize),
+}
+
+enum Output {
+    Solutions(PartSolution, PartSolution),
+    Report(String),
+}
+
+struct Config {
+    day: usize,
+    path: String,
+    window: Option<usize>,
+    mode: Option<day01::DepthMode>,
+    tolerance: Option<f64>,
+    stream: bool,
+    report: bool,
+    threshold: Option<f64>,
+    trajectory: Option<day02::Part>,
+    svg: bool,
+    models: Vec<String>,
+    target: Option<(i64, i64)>,
+    allow_back: bool,
+    pad: bool,
+    criteria: Vec<day03::BitCriteria>,
+    win_rules: Vec<day04::WinRule>,
 }
 
-fn cli_args() -> Result<(usize, String), ApplicationError> {
+fn cli_args() -> Result<Config, ApplicationError> {
     let day_arg = clap::Arg::new("day")
-        .about("Which day's solutions to compute")
+        .help("Which day's solutions to compute")
         .short('d')
         .long("day")
         .value_name("DAY")
         .required(true);
 
     let input_arg = clap::Arg::new("input")
-        .about("What input data to use when computing the day's solution")
+        .help("What input data to use when computing the day's solution")
         .short('i')
         .long("input")
         .value_name("FILE")
         .required(false);
 
+    let window_arg = clap::Arg::new("window")
+        .help("Size of the sliding window used for day 1 solution 2")
+        .short('w')
+        .long("window")
+        .value_name("SIZE")
+        .required(false);
+
+    let mode_arg = clap::Arg::new("mode")
+        .help("How day 1 depths are parsed: unsigned, signed or decimal")
+        .long("mode")
+        .value_name("MODE")
+        .required(false);
+
+    let tolerance_arg = clap::Arg::new("tolerance")
+        .help("How much deeper a day 1 reading must be to count as an increase")
+        .long("tolerance")
+        .value_name("DEPTH")
+        .required(false);
+
+    let stream_arg = clap::Arg::new("stream")
+        .help("Analyse day 1 input one line at a time, reading stdin when FILE is -")
+        .long("stream")
+        .takes_value(false)
+        .required(false);
+
+    let report_arg = clap::Arg::new("report")
+        .help("Print a report for days 1 to 4 instead of the solutions")
+        .long("report")
+        .takes_value(false)
+        .conflicts_with("stream")
+        .required(false);
+
+    let threshold_arg = clap::Arg::new("threshold")
+        .help("How far a day 1 reading may stray from the rolling average before it is reported")
+        .long("threshold")
+        .value_name("DEPTH")
+        .requires("report")
+        .required(false);
+
+    let trajectory_arg = clap::Arg::new("trajectory")
+        .help("Print the day 2 trajectory of the given solution's submarine as CSV")
+        .long("trajectory")
+        .value_name("PART")
+        .conflicts_with_all(&["stream", "report"])
+        .required(false);
+
+    let svg_arg = clap::Arg::new("svg")
+        .help("Draw the day 2 submarine paths as an SVG image")
+        .long("svg")
+        .takes_value(false)
+        .conflicts_with_all(&["stream", "report", "trajectory"])
+        .required(false);
+
+    let model_arg = clap::Arg::new("model")
+        .help("Run the day 2 course under the named submarine models, or all of them")
+        .long("model")
+        .value_name("MODEL")
+        .multiple_occurrences(true)
+        .conflicts_with_all(&["stream", "report", "trajectory", "svg"])
+        .required(false);
+
+    let plan_arg = clap::Arg::new("plan")
+        .help("Plan the shortest day 2 course to a position and depth, given as X,DEPTH")
+        .long("plan")
+        .value_name("TARGET")
+        .allow_hyphen_values(true)
+        .conflicts_with_all(&["stream", "report", "trajectory", "svg", "model"])
+        .required(false);
+
+    let allow_back_arg = clap::Arg::new("allow-back")
+        .help("Let a planned day 2 course back up")
+        .long("allow-back")
+        .takes_value(false)
+        .requires("plan")
+        .required(false);
+
+    let pad_arg = clap::Arg::new("pad")
+        .help("Left-pad day 3 lines with zeros to the widest line instead of rejecting them")
+        .long("pad")
+        .takes_value(false)
+        .required(false);
+
+    let criteria_arg = clap::Arg::new("criteria")
+        .help("Print the day 3 rating kept by a bit criteria such as most, least or least:1")
+        .long("criteria")
+        .value_name("CRITERIA")
+        .multiple_occurrences(true)
+        .conflicts_with("report")
+        .required(false);
+
+    let win_rule_arg = clap::Arg::new("win-rule")
+        .help(
+            "Day 4 pattern that wins a bingo board: rows, columns, diagonals, corners or blackout",
+        )
+        .long("win-rule")
+        .value_name("RULE")
+        .multiple_occurrences(true)
+        .required(false);
+
     let matches = clap::App::new("Advent of Code 2021")
         .author("Alex Bechanko")
         .about("Compute solutions to Advent of Code 2021")
         .arg(day_arg)
         .arg(input_arg)
+        .arg(window_arg)
+        .arg(mode_arg)
+        .arg(tolerance_arg)
+        .arg(stream_arg)
+        .arg(report_arg)
+        .arg(threshold_arg)
+        .arg(trajectory_arg)
+        .arg(svg_arg)
+        .arg(model_arg)
+        .arg(plan_arg)
+        .arg(allow_back_arg)
+        .arg(pad_arg)
+        .arg(criteria_arg)
+        .arg(win_rule_arg)
         .get_matches();
 
     let day = matches
@@ -66,9 +227,94 @@ fn cli_args() -> Result<(usize, String), ApplicationError> {
         .map(|inp| inp.to_string())
         .unwrap_or(default_path);
 
-    let data = read_file(path)?;
+    let window = match matches.value_of("window") {
+        Some(ws) => match ws.parse::<usize>() {
+            Ok(w) if w > 0 => Some(w),
+            _ => return Err(ApplicationError::InvalidWindow),
+        },
+        None => None,
+    };
+
+    let mode = match matches.value_of("mode") {
+        Some(ms) => Some(ms.parse().map_err(ApplicationError::InvalidMode)?),
+        None => None,
+    };
+
+    let tolerance = match matches.value_of("tolerance") {
+        Some(ts) => match ts.parse::<f64>() {
+            Ok(t) if t >= 0.0 => Some(t),
+            _ => return Err(ApplicationError::InvalidTolerance),
+        },
+        None => None,
+    };
+
+    let threshold = match matches.value_of("threshold") {
+        Some(ts) => match ts.parse::<f64>() {
+            Ok(t) if t >= 0.0 => Some(t),
+            _ => return Err(ApplicationError::InvalidThreshold),
+        },
+        None => None,
+    };
 
-    return Ok((day, data));
+    let trajectory = match matches.value_of("trajectory") {
+        Some(ps) => match ps.parse::<usize>() {
+            Ok(1) => Some(day02::Part::Solution1),
+            Ok(2) => Some(day02::Part::Solution2),
+            _ => return Err(ApplicationError::InvalidTrajectory),
+        },
+        None => None,
+    };
+
+    let target = match matches.value_of("plan") {
+        Some(ts) => {
+            let parsed = ts
+                .split_once(',')
+                .and_then(|(x, d)| Some((x.trim().parse().ok()?, d.trim().parse().ok()?)));
+            Some(parsed.ok_or(ApplicationError::InvalidTarget)?)
+        }
+        None => None,
+    };
+
+    let stream = matches.is_present("stream");
+    let report = matches.is_present("report");
+    let svg = matches.is_present("svg");
+    let models = matches
+        .values_of("model")
+        .map(|ms| ms.map(|m| m.to_string()).collect())
+        .unwrap_or_default();
+    let allow_back = matches.is_present("allow-back");
+    let pad = matches.is_present("pad");
+    let criteria = matches
+        .values_of("criteria")
+        .map(|cs| cs.map(|c| c.parse()).collect::<Result<Vec<_>, String>>())
+        .transpose()
+        .map_err(ApplicationError::InvalidCriteria)?
+        .unwrap_or_default();
+    let win_rules = matches
+        .values_of("win-rule")
+        .map(|rs| rs.map(|r| r.parse()).collect::<Result<Vec<_>, String>>())
+        .transpose()
+        .map_err(ApplicationError::InvalidWinRule)?
+        .unwrap_or_default();
+
+    return Ok(Config {
+        day,
+        path,
+        window,
+        mode,
+        tolerance,
+        stream,
+        report,
+        threshold,
+        trajectory,
+        svg,
+        models,
+        target,
+        allow_back,
+        pad,
+        criteria,
+        win_rules,
+    });
 }
 
 fn read_file(path: String) -> Result<String, ApplicationError> {
@@ -84,40 +330,278 @@ fn read_file(path: String) -> Result<String, ApplicationError> {
     return Ok(data);
 }
 
-fn advent_day_solution((day, data): (usize, String)) -> Result<(String, String), ApplicationError> {
-    let solutions: Vec<&dyn Fn(&str) -> Result<(String, String), String>> = vec![
-        &day01::solutions,
-        &day02::solutions,
-        &day03::solutions,
-        &day04::solutions,
-        &day05::solutions,
-        &day06::solutions,
-    ];
+fn stream_solution(
+    path: String,
+    options: &day01::Options,
+) -> Result<(String, String), ApplicationError> {
+    let solution = if path == "-" {
+        day01::stream_solutions(std::io::stdin().lock(), options)
+    } else {
+        let file = std::fs::File::open(&path)
+            .ok()
+            .ok_or(ApplicationError::InvalidDataFile(path.clone()))?;
+        day01::stream_solutions(std::io::BufReader::new(file), options)
+    };
+
+    return solution.map_err(ApplicationError::SolutionParseError);
+}
 
-    let solution = solutions
-        .get(day - 1)
-        .ok_or(ApplicationError::OutOfRangeDay(day))?;
+fn require_day(option: &str, day: usize, supported: usize) -> Result<(), ApplicationError> {
+    if day != supported {
+        return Err(ApplicationError::UnsupportedOption(option.to_string(), day));
+    }
 
-    return solution(&data).map_err(|why| ApplicationError::SolutionParseError(why));
+    return Ok(());
 }
 
-fn main() {
-    match cli_args().and_then(advent_day_solution) {
-        Ok((soln1, soln2)) => {
-            println!("Solution 1 answer: {}", soln1);
-            println!("Solution 2 answer: {}", soln2);
+fn advent_day_solution(config: Config) -> Result<Output, ApplicationError> {
+    let Config {
+        day,
+        path,
+        window,
+        mode,
+        tolerance,
+        stream,
+        report,
+        threshold,
+        trajectory,
+        svg,
+        models,
+        target,
+        allow_back,
+        pad,
+        criteria,
+        win_rules,
+    } = config;
+
+    // every day specific option is checked before dispatching, so none are silently ignored
+    let day_options = [
+        ("--window", window.is_some(), 1),
+        ("--mode", mode.is_some(), 1),
+        ("--tolerance", tolerance.is_some(), 1),
+        ("--stream", stream, 1),
+        ("--threshold", threshold.is_some(), 1),
+        ("--plan", target.is_some(), 2),
+        ("--trajectory", trajectory.is_some(), 2),
+        ("--svg", svg, 2),
+        ("--model", !models.is_empty(), 2),
+        ("--pad", pad, 3),
+        ("--criteria", !criteria.is_empty(), 3),
+        ("--win-rule", !win_rules.is_empty(), 4),
+    ];
+    for (option, _, supported) in day_options.iter().filter(|(_, present, _)| *present) {
+        require_day(option, day, *supported)?;
+    }
+    let custom_depth_options = window.is_some() || mode.is_some() || tolerance.is_some();
+
+    let defaults = day01::Options::default();
+    let depth_options = day01::Options {
+        window: window.unwrap_or(defaults.window),
+        mode: mode.unwrap_or(defaults.mode),
+        tolerance: tolerance.unwrap_or(defaults.tolerance),
+    };
+
+    if stream {
+        let (soln1, soln2) = stream_solution(path, &depth_options)?;
+        return Ok(Output::Solutions(Ok(soln1), Ok(soln2)));
+    }
+
+    if let Some((x, depth)) = target {
+        return day02::plan(x, depth, allow_back)
+            .map(Output::Report)
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    let data = read_file(path)?;
+
+    if let Some(part) = trajectory {
+        return day02::trajectory_csv(&data, part)
+            .map(Output::Report)
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if svg {
+        return day02::svg(&data)
+            .map(Output::Report)
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if !models.is_empty() {
+        let names = models.iter().map(|m| m.as_str()).collect::<Vec<&str>>();
+        return day02::compare_models(&data, &names)
+            .map(Output::Report)
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if report {
+        let report = match day {
+            1 => day01::report(
+                &data,
+                &depth_options,
+                threshold.unwrap_or(day01::DEFAULT_ANOMALY_THRESHOLD),
+            ),
+            2 => day02::report(&data),
+            3 => day03::report(&data, pad),
+            4 if win_rules.is_empty() => day04::report(&data, &day04::WinRule::STANDARD),
+            4 => day04::report(&data, &win_rules),
+            _ => {
+                return Err(ApplicationError::UnsupportedOption(
+                    "--report".to_string(),
+                    day,
+                ))
+            }
+        };
+
+        return report
+            .map(Output::Report)
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if custom_depth_options {
+        return day01::solutions_with(&data, &depth_options)
+            .map(|(soln1, soln2)| Output::Solutions(Ok(soln1), Ok(soln2)))
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if !criteria.is_empty() {
+        return day03::ratings(&data, pad, &criteria)
+            .map(Output::Report)
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if !win_rules.is_empty() {
+        return day04::solutions_with(&data, &win_rules)
+            .map(|(soln1, soln2)| Output::Solutions(Ok(soln1), Ok(soln2)))
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    if pad {
+        return day03::solutions_with(&data, pad)
+            .map(|(soln1, soln2)| Output::Solutions(soln1, soln2))
+            .map_err(ApplicationError::SolutionParseError);
+    }
+
+    let solution: &DaySolution = match day {
+        1 => &day01::solutions,
+        2 => &day02::solutions,
+        3 => {
+            return day03::solutions(&data)
+                .map(|(soln1, soln2)| Output::Solutions(soln1, soln2))
+                .map_err(ApplicationError::SolutionParseError)
         }
-        Err(ApplicationError::SolutionParseError(why)) => {
+        4 => &day04::solutions,
+        5 => &day05::solutions,
+        6 => &day06::solutions,
+        20 => &day20::solutions,
+        21 => &day21::solutions,
+        22 => &day22::solutions,
+        23 => &day23::solutions,
+        24 => &day24::solutions,
+        25 => &day25::solutions,
+        _ => return Err(ApplicationError::OutOfRangeDay(day)),
+    };
+
+    return solution(&data)
+        .map(|(soln1, soln2)| Output::Solutions(Ok(soln1), Ok(soln2)))
+        .map_err(ApplicationError::SolutionParseError);
+}
+
+fn print_error(error: ApplicationError) {
+    match error {
+        ApplicationError::SolutionParseError(why) => {
             println!("{}", why);
         }
-        Err(ApplicationError::InvalidDay) => {
+        ApplicationError::InvalidDay => {
             println!("An invalid day was passed as an argument. Day values must be a number between 1 and 25");
         }
-        Err(ApplicationError::InvalidDataFile(name)) => {
+        ApplicationError::InvalidDataFile(name) => {
             println!("An error occurred trying to read from file {}. Please make sure the file exists and is readable.", name);
         }
-        Err(ApplicationError::OutOfRangeDay(day)) => {
-            println!("The day {} is currently not implemented. Please try a day closer to the beginning.", day);
+        ApplicationError::InvalidWindow => {
+            println!("An invalid window was passed as an argument. Window sizes must be a positive number");
+        }
+        ApplicationError::InvalidThreshold => {
+            println!("An invalid threshold was passed as an argument. Thresholds must be a non-negative number");
+        }
+        ApplicationError::InvalidMode(why)
+        | ApplicationError::InvalidCriteria(why)
+        | ApplicationError::InvalidWinRule(why) => {
+            println!("{}", why);
+        }
+        ApplicationError::InvalidTolerance => {
+            println!("An invalid tolerance was passed as an argument. Tolerances must be a non-negative number");
+        }
+        ApplicationError::InvalidTrajectory => {
+            println!("An invalid trajectory was passed as an argument. Trajectories must be solution 1 or 2");
         }
+        ApplicationError::InvalidTarget => {
+            println!("An invalid target was passed as an argument. Targets must be a position and depth such as 15,60");
+        }
+        ApplicationError::UnsupportedOption(option, day) => {
+            println!("The option {} is not supported by day {}.", option, day);
+        }
+        ApplicationError::OutOfRangeDay(day) => {
+            println!(
+                "The day {} is currently not implemented. Please try another day.",
+                day
+            );
+        }
+    }
+}
+
+fn exit_status(output: &Result<Output, ApplicationError>) -> i32 {
+    return match output {
+        Ok(Output::Solutions(soln1, soln2)) if soln1.is_err() || soln2.is_err() => {
+            PART_FAILURE_STATUS
+        }
+        Ok(_) => 0,
+        Err(_) => ERROR_STATUS,
+    };
+}
+
+fn main() {
+    let output = cli_args().and_then(advent_day_solution);
+    let status = exit_status(&output);
+    match output {
+        Ok(Output::Solutions(soln1, soln2)) => {
+            for (part, soln) in [(1, soln1), (2, soln2)] {
+                match soln {
+                    Ok(answer) => println!("Solution {} answer: {}", part, answer),
+                    Err(why) => println!("Solution {} failed: {}", part, why),
+                }
+            }
+        }
+        Ok(Output::Report(report)) => println!("{}", report),
+        Err(error) => print_error(error),
+    }
+
+    std::process::exit(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_status() {
+        let solved = Ok(Output::Solutions(Ok("1".to_string()), Ok("2".to_string())));
+        assert_eq!(exit_status(&solved), 0);
+        assert_eq!(exit_status(&Ok(Output::Report("report".to_string()))), 0);
+        assert_eq!(exit_status(&Err(ApplicationError::InvalidDay)), 1);
+
+        let failed = Ok(Output::Solutions(
+            Ok("1".to_string()),
+            Err("why".to_string()),
+        ));
+        assert_eq!(exit_status(&failed), 3);
+        let failed = Ok(Output::Solutions(
+            Err("why".to_string()),
+            Ok("2".to_string()),
+        ));
+        assert_eq!(exit_status(&failed), 3);
+
+        // clap exits with 2 on usage errors, so a failed part must not
+        assert_ne!(PART_FAILURE_STATUS, 2);
+        assert_ne!(PART_FAILURE_STATUS, ERROR_STATUS);
     }
 }